@@ -0,0 +1,54 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use polars_core::frame::DataFrame;
+use polars_core::frame::column::Column;
+use polars_serde::de::BorrowedDataFrameDeserializer;
+use serde::Deserialize;
+use std::hint::black_box;
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Row<'a> {
+    id: u64,
+    name: &'a str,
+    score: Option<f64>,
+}
+
+fn chunk(offset: u64, len: u64) -> DataFrame {
+    let ids = (offset..offset + len).collect::<Vec<_>>();
+    let names = ids.iter().map(|i| format!("name-{i}")).collect::<Vec<_>>();
+    let scores = ids
+        .iter()
+        .map(|i| (i % 3 != 0).then_some(*i as f64 / 2.0))
+        .collect::<Vec<_>>();
+    DataFrame::new_infer_height(vec![
+        Column::new("id".into(), ids),
+        Column::new("name".into(), names),
+        Column::new("score".into(), scores),
+    ])
+    .unwrap()
+}
+
+fn bench_rows(c: &mut Criterion) {
+    const HEIGHT: u64 = 100_000;
+
+    let mut group = c.benchmark_group("rows");
+    for n_chunks in [1, 100, 1000] {
+        let len = HEIGHT / n_chunks;
+        let mut df = chunk(0, len);
+        for i in 1..n_chunks {
+            df.vstack_mut(&chunk(i * len, len)).unwrap();
+        }
+        assert_eq!(df.first_col_n_chunks(), n_chunks as usize);
+
+        group.bench_function(format!("{n_chunks} chunks"), |b| {
+            b.iter(|| {
+                Vec::<Row<'_>>::deserialize(BorrowedDataFrameDeserializer::rows(black_box(&df)))
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_rows);
+criterion_main!(benches);
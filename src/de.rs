@@ -1,16 +1,59 @@
 #[macro_use]
 mod macros;
 mod any_value;
+#[cfg(feature = "ndarray")]
+mod array2;
+#[cfg(feature = "rows")]
+mod batch;
 mod data_frame;
+mod fields;
+#[cfg(feature = "group-by")]
+mod grouped;
+mod key;
+#[cfg(feature = "rows")]
+mod keyed;
+#[cfg(feature = "lazy")]
+mod lazy;
+#[cfg(feature = "rows")]
+mod nested;
+#[cfg(feature = "object")]
+mod object;
+#[cfg(feature = "parallel")]
+mod par;
 #[cfg(feature = "rows")]
 mod row;
+#[cfg(feature = "transcode")]
+mod transcode;
 
 pub use any_value::{
-    BorrowedDeserializer as BorrowedAnyValueDeserializer, Deserializer as AnyValueDeserializer,
+    BorrowedDeserializer as BorrowedAnyValueDeserializer, CategoricalRepr,
+    Deserializer as AnyValueDeserializer,
 };
+#[cfg(feature = "ndarray")]
+pub use array2::to_array2;
+#[cfg(feature = "rows")]
+pub use batch::BatchRowIter;
+#[cfg(feature = "dtype-categorical")]
+pub use data_frame::categories;
+pub(crate) use data_frame::cells;
+#[cfg(feature = "rows")]
+pub use data_frame::from_rows;
 pub use data_frame::{
     BorrowedDeserializer as BorrowedDataFrameDeserializer, Deserializer as DataFrameDeserializer,
+    from_columns,
 };
+pub use fields::field_names;
+pub use key::KeyNormalizer;
+#[cfg(feature = "rows")]
+pub use keyed::DuplicateKeys;
+#[cfg(feature = "lazy")]
+pub use lazy::LazyFrameDeserializer;
+#[cfg(all(feature = "lazy", feature = "rows"))]
+pub use lazy::from_lazy;
+#[cfg(feature = "object")]
+pub use object::register_object;
+#[cfg(feature = "parallel")]
+pub use par::ParColumns;
 use polars_core::datatypes::AnyValue;
 #[cfg(feature = "rows")]
 pub use row::{BorrowedDeserializer as BorrowedRowDeserializer, Deserializer as RowDeserializer};
@@ -18,6 +61,8 @@ use serde::de;
 use std::fmt;
 #[cfg(feature = "dtype-categorical")]
 use std::sync::Arc;
+#[cfg(feature = "transcode")]
+pub use transcode::transcode;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -29,6 +74,9 @@ pub enum Error {
         polars_core::datatypes::CatSize,
         Arc<polars_core::datatypes::CategoricalMapping>,
     ),
+    #[cfg(feature = "object")]
+    #[error("unregistered object type {0}")]
+    UnregisteredObject(&'static str),
     #[error("unknown data type")]
     UnknownDataType(AnyValue<'static>),
     #[error("{0}")]
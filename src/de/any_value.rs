@@ -7,6 +7,7 @@ macro_rules! deserialize_any {
         where
             V: de::Visitor<'de>,
         {
+            let categorical = self.1;
             match self.0 {
                 AnyValue::Null => visitor.visit_none(),
                 AnyValue::Boolean(v) => visitor.visit_bool(v),
@@ -21,51 +22,98 @@ macro_rules! deserialize_any {
                 AnyValue::Int32(v) => visitor.visit_i32(v),
                 AnyValue::Int64(v) => visitor.visit_i64(v),
                 AnyValue::Int128(v) => visitor.visit_i128(v),
+                AnyValue::Float16(v) => visitor.visit_f32(v.into()),
                 AnyValue::Float32(v) => visitor.visit_f32(v),
                 AnyValue::Float64(v) => visitor.visit_f64(v),
+                #[cfg(feature = "dtype-date")]
+                AnyValue::Date(v) => visitor.visit_i32(v),
+                #[cfg(feature = "dtype-datetime")]
+                AnyValue::Datetime(v, _, _) | AnyValue::DatetimeOwned(v, _, _) => {
+                    visitor.visit_i64(v)
+                }
+                #[cfg(feature = "dtype-duration")]
+                AnyValue::Duration(v, _) => visitor.visit_i64(v),
+                #[cfg(feature = "dtype-time")]
+                AnyValue::Time(v) => visitor.visit_i64(v),
+                #[cfg(feature = "dtype-decimal")]
+                AnyValue::Decimal(v, _, scale) => {
+                    visitor.visit_string(crate::value::format_decimal(v, scale))
+                }
+                #[cfg(feature = "dtype-categorical")]
+                AnyValue::Categorical(cat, _) | AnyValue::Enum(cat, _)
+                    if categorical == CategoricalRepr::Code =>
+                {
+                    visitor.visit_u32(cat)
+                }
                 #[cfg(feature = "dtype-categorical")]
                 AnyValue::Categorical(cat, categorical_mapping)
                 | AnyValue::Enum(cat, categorical_mapping) => {
-                    if let Some(v) = categorical_mapping.cat_to_str(cat) {
-                        visitor.$visit_str(v)
-                    } else {
-                        Err(super::Error::InvalidCategoricalId(
+                    match categorical_mapping.cat_to_str(cat) {
+                        Some(v) if categorical == CategoricalRepr::String => visitor.$visit_str(v),
+                        Some(v) => visitor.visit_map(de::value::MapDeserializer::new(
+                            [
+                                ("code", Self::new(AnyValue::UInt32(cat))),
+                                ("label", Self::new(AnyValue::String(v))),
+                            ]
+                            .into_iter(),
+                        )),
+                        None => Err(super::Error::InvalidCategoricalId(
                             cat,
                             categorical_mapping.clone(),
-                        ))
+                        )),
                     }
                 }
                 #[cfg(feature = "dtype-categorical")]
+                AnyValue::CategoricalOwned(cat, _) | AnyValue::EnumOwned(cat, _)
+                    if categorical == CategoricalRepr::Code =>
+                {
+                    visitor.visit_u32(cat)
+                }
+                #[cfg(feature = "dtype-categorical")]
                 AnyValue::CategoricalOwned(cat, categorical_mapping)
                 | AnyValue::EnumOwned(cat, categorical_mapping) => {
-                    if let Some(v) = categorical_mapping.cat_to_str(cat) {
-                        visitor.visit_str(v)
-                    } else {
-                        Err(super::Error::InvalidCategoricalId(cat, categorical_mapping))
+                    match categorical_mapping.cat_to_str(cat) {
+                        Some(v) if categorical == CategoricalRepr::String => visitor.visit_str(v),
+                        Some(v) => visitor.visit_map(de::value::MapDeserializer::new(
+                            [
+                                ("code", Self::new(AnyValue::UInt32(cat))),
+                                ("label", Self::new(AnyValue::StringOwned(v.into()))),
+                            ]
+                            .into_iter(),
+                        )),
+                        None => Err(super::Error::InvalidCategoricalId(cat, categorical_mapping)),
                     }
                 }
                 AnyValue::List(v) => visitor.visit_seq(de::value::SeqDeserializer::new(
-                    v.iter().map(Deserializer::new),
+                    v.iter().map(|v| Deserializer(v, categorical)),
                 )),
                 #[cfg(feature = "dtype-array")]
                 AnyValue::Array(v, _) => visitor.visit_seq(de::value::SeqDeserializer::new(
-                    v.iter().map(Deserializer::new),
+                    v.iter().map(|v| Deserializer(v, categorical)),
                 )),
                 #[cfg(feature = "dtype-struct")]
                 AnyValue::Struct(_, _, _) => {
                     // use `into_static` since extracting borrowed values from a `StructArray` is too complicated.
                     // https://github.com/pola-rs/polars/blob/0d720b3a475ade851e451dbb26bf11310014dd0e/crates/polars-core/src/datatypes/any_value.rs#L1477-L1494
-                    Self::new(self.0.into_static()).deserialize_any(visitor)
+                    Self(self.0.into_static(), categorical).deserialize_any(visitor)
                 }
                 #[cfg(feature = "dtype-struct")]
                 AnyValue::StructOwned(v) => visitor.visit_map(de::value::MapDeserializer::new(
                     v.1.into_iter()
                         .map(|field| de::value::StringDeserializer::new(field.name.into_string()))
-                        .zip(v.0.into_iter().map(Self::new)),
+                        .zip(v.0.into_iter().map(|v| Self(v, categorical))),
                 )),
                 AnyValue::StringOwned(v) => visitor.visit_string(v.into_string()),
                 AnyValue::Binary(v) => visitor.$visit_bytes(v),
                 AnyValue::BinaryOwned(v) => visitor.visit_byte_buf(v),
+                #[cfg(feature = "object")]
+                AnyValue::Object(v) => {
+                    de::Deserializer::deserialize_any(super::object::to_value(v)?, visitor)
+                }
+                #[cfg(feature = "object")]
+                AnyValue::ObjectOwned(v) => {
+                    de::Deserializer::deserialize_any(super::object::to_value(&*v.0)?, visitor)
+                }
                 #[allow(unreachable_patterns)]
                 _ => Err(super::Error::UnknownDataType(self.0.into_static())),
             }
@@ -73,11 +121,242 @@ macro_rules! deserialize_any {
     };
 }
 
-pub struct Deserializer<'a>(AnyValue<'a>);
+// strings and binary values are converted into each other when the visitor asks for the other one
+macro_rules! deserialize_str_and_bytes {
+    ($visit_str:ident, $visit_bytes:ident) => {
+        fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            match self.0 {
+                AnyValue::Binary(v) => match std::str::from_utf8(v) {
+                    Ok(v) => visitor.$visit_str(v),
+                    Err(_) => Err(de::Error::invalid_value(de::Unexpected::Bytes(v), &visitor)),
+                },
+                AnyValue::BinaryOwned(v) => match String::from_utf8(v) {
+                    Ok(v) => visitor.visit_string(v),
+                    Err(e) => Err(de::Error::invalid_value(
+                        de::Unexpected::Bytes(e.as_bytes()),
+                        &visitor,
+                    )),
+                },
+                _ => self.deserialize_any(visitor),
+            }
+        }
+
+        fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            self.deserialize_str(visitor)
+        }
+
+        fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            match self.0 {
+                AnyValue::String(v) => visitor.$visit_bytes(v.as_bytes()),
+                AnyValue::StringOwned(v) => visitor.visit_byte_buf(v.into_string().into_bytes()),
+                _ => self.deserialize_any(visitor),
+            }
+        }
+
+        fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            self.deserialize_bytes(visitor)
+        }
+
+        // e.g. `Vec<u8>`, which only accepts sequences
+        fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            match self.0 {
+                AnyValue::String(v) => {
+                    visitor.visit_seq(de::value::SeqDeserializer::new(v.bytes()))
+                }
+                AnyValue::StringOwned(v) => {
+                    visitor.visit_seq(de::value::SeqDeserializer::new(v.bytes()))
+                }
+                AnyValue::Binary(v) => {
+                    visitor.visit_seq(de::value::SeqDeserializer::new(v.iter().copied()))
+                }
+                AnyValue::BinaryOwned(v) => {
+                    visitor.visit_seq(de::value::SeqDeserializer::new(v.into_iter()))
+                }
+                _ => self.deserialize_any(visitor),
+            }
+        }
+    };
+}
+
+// 128-bit integers are narrowed for visitors that ask for a smaller type
+macro_rules! deserialize_integer {
+    () => {
+        deserialize_integer! {
+            deserialize_i8 => visit_i8(i8),
+            deserialize_i16 => visit_i16(i16),
+            deserialize_i32 => visit_i32(i32),
+            deserialize_i64 => visit_i64(i64),
+            deserialize_i128 => visit_i128(i128),
+            deserialize_u8 => visit_u8(u8),
+            deserialize_u16 => visit_u16(u16),
+            deserialize_u32 => visit_u32(u32),
+            deserialize_u64 => visit_u64(u64),
+            deserialize_u128 => visit_u128(u128),
+        }
+    };
+    ($($method:ident => $visit:ident($ty:ty),)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: de::Visitor<'de>,
+            {
+                match self.0 {
+                    AnyValue::Int128(v) => match <$ty>::try_from(v) {
+                        Ok(v) => visitor.$visit(v),
+                        Err(_) => Err(de::Error::custom(format_args!(
+                            "integer {v} is out of range for {}",
+                            stringify!($ty),
+                        ))),
+                    },
+                    AnyValue::UInt128(v) => match <$ty>::try_from(v) {
+                        Ok(v) => visitor.$visit(v),
+                        Err(_) => Err(de::Error::custom(format_args!(
+                            "integer {v} is out of range for {}",
+                            stringify!($ty),
+                        ))),
+                    },
+                    _ => self.deserialize_any(visitor),
+                }
+            }
+        )*
+    };
+}
+
+// decimals are exact strings unless a float is asked for
+macro_rules! deserialize_float {
+    () => {
+        fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            self.deserialize_f64(visitor)
+        }
+
+        fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            match self.0 {
+                #[cfg(feature = "dtype-decimal")]
+                AnyValue::Decimal(v, _, scale) => visitor.visit_f64(
+                    crate::value::format_decimal(v, scale)
+                        .parse()
+                        .unwrap_or(f64::NAN),
+                ),
+                _ => self.deserialize_any(visitor),
+            }
+        }
+    };
+}
+
+// chars are stored as single-character strings or as their code points
+macro_rules! deserialize_char {
+    () => {
+        fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            let v = match &self.0 {
+                AnyValue::String(v) => *v,
+                AnyValue::StringOwned(v) => v.as_str(),
+                AnyValue::UInt32(v) => {
+                    return match char::from_u32(*v) {
+                        Some(v) => visitor.visit_char(v),
+                        None => Err(de::Error::invalid_value(
+                            de::Unexpected::Unsigned((*v).into()),
+                            &"a unicode code point",
+                        )),
+                    };
+                }
+                _ => return self.deserialize_any(visitor),
+            };
+            // a `char` is one code point, so graphemes of several (e.g. "e\u{301}") are rejected
+            let mut chars = v.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => visitor.visit_char(c),
+                (Some(_), Some(_)) => Err(de::Error::custom(format_args!(
+                    "invalid value: string {v:?} of {} code points, expected a single code point \
+                     (a char cannot hold a multi-code-point grapheme)",
+                    v.chars().count(),
+                ))),
+                (None, _) => Err(de::Error::invalid_value(
+                    de::Unexpected::Str(v),
+                    &"a single code point",
+                )),
+            }
+        }
+    };
+}
+
+// `Value` asks for a newtype struct to read logical values with their data types
+macro_rules! deserialize_newtype_struct {
+    () => {
+        fn deserialize_newtype_struct<V>(
+            self,
+            name: &'static str,
+            visitor: V,
+        ) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            if name == crate::value::TOKEN {
+                #[cfg(feature = "dtype-array")]
+                if let AnyValue::Array(_, _) = self.0 {
+                    return crate::value::visit_tagged(visitor, crate::value::ARRAY, self);
+                }
+                match crate::Value::logical(&self.0)? {
+                    #[cfg(feature = "dtype-categorical")]
+                    Some(crate::Value::Categorical(_) | crate::Value::Enum(_))
+                        if self.1 != CategoricalRepr::String => {}
+                    Some(v) => return v.deserialize_newtype_struct(name, visitor),
+                    None => {}
+                }
+            }
+            visitor.visit_newtype_struct(self)
+        }
+    };
+}
+
+/// How `Categorical` and `Enum` values are presented.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CategoricalRepr {
+    /// The label.
+    #[default]
+    String,
+    /// The physical code as a `u32`.
+    Code,
+    /// A map of `code` and `label`.
+    Both,
+}
+
+/// Deserializes a single cell.
+///
+/// A `char` is read from a `UInt32` code point or from a string of exactly one code point,
+/// so graphemes made of several code points are rejected.
+pub struct Deserializer<'a>(AnyValue<'a>, CategoricalRepr);
 
 impl<'a> Deserializer<'a> {
     pub fn new(value: AnyValue<'a>) -> Self {
-        Self(value)
+        Self(value, CategoricalRepr::default())
+    }
+
+    pub fn with_categorical(self, categorical: CategoricalRepr) -> Self {
+        Self(self.0, categorical)
     }
 }
 
@@ -85,6 +364,11 @@ impl<'de, 'a> de::Deserializer<'de> for Deserializer<'a> {
     type Error = super::Error;
 
     deserialize_any!(visit_str, visit_bytes);
+    deserialize_str_and_bytes!(visit_str, visit_bytes);
+    deserialize_char!();
+    deserialize_integer!();
+    deserialize_float!();
+    deserialize_newtype_struct!();
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
@@ -97,7 +381,46 @@ impl<'de, 'a> de::Deserializer<'de> for Deserializer<'a> {
         }
     }
 
-    deserialize_delegate!();
+    // unit variants are stored as their names (e.g. in `Enum` columns)
+    fn deserialize_enum<V>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    deserialize_delegate! {
+        deserialize_bool
+        deserialize_unit
+        deserialize_unit_struct
+        deserialize_tuple
+        deserialize_tuple_struct
+        deserialize_map
+        deserialize_struct
+        deserialize_identifier
+        deserialize_ignored_any
+    }
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for Deserializer<'a> {
+    type Error = super::Error;
+    type Variant = UnitVariant;
+
+    // variants are matched by name whatever the categorical representation
+    fn variant_seed<T>(self, seed: T) -> Result<(T::Value, Self::Variant), Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        Ok((
+            seed.deserialize(Self(self.0, CategoricalRepr::String))?,
+            UnitVariant,
+        ))
+    }
 }
 
 impl<'de, 'a> de::IntoDeserializer<'de, super::Error> for Deserializer<'a> {
@@ -108,11 +431,15 @@ impl<'de, 'a> de::IntoDeserializer<'de, super::Error> for Deserializer<'a> {
     }
 }
 
-pub struct BorrowedDeserializer<'de>(AnyValue<'de>);
+pub struct BorrowedDeserializer<'de>(AnyValue<'de>, CategoricalRepr);
 
 impl<'de> BorrowedDeserializer<'de> {
     pub fn new(value: AnyValue<'de>) -> Self {
-        Self(value)
+        Self(value, CategoricalRepr::default())
+    }
+
+    pub fn with_categorical(self, categorical: CategoricalRepr) -> Self {
+        Self(self.0, categorical)
     }
 }
 
@@ -120,6 +447,11 @@ impl<'de> de::Deserializer<'de> for BorrowedDeserializer<'de> {
     type Error = super::Error;
 
     deserialize_any!(visit_borrowed_str, visit_borrowed_bytes);
+    deserialize_str_and_bytes!(visit_borrowed_str, visit_borrowed_bytes);
+    deserialize_char!();
+    deserialize_integer!();
+    deserialize_float!();
+    deserialize_newtype_struct!();
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
@@ -132,7 +464,44 @@ impl<'de> de::Deserializer<'de> for BorrowedDeserializer<'de> {
         }
     }
 
-    deserialize_delegate!();
+    fn deserialize_enum<V>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    deserialize_delegate! {
+        deserialize_bool
+        deserialize_unit
+        deserialize_unit_struct
+        deserialize_tuple
+        deserialize_tuple_struct
+        deserialize_map
+        deserialize_struct
+        deserialize_identifier
+        deserialize_ignored_any
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for BorrowedDeserializer<'de> {
+    type Error = super::Error;
+    type Variant = UnitVariant;
+
+    fn variant_seed<T>(self, seed: T) -> Result<(T::Value, Self::Variant), Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        Ok((
+            seed.deserialize(Self(self.0, CategoricalRepr::String))?,
+            UnitVariant,
+        ))
+    }
 }
 
 impl<'de> de::IntoDeserializer<'de, super::Error> for BorrowedDeserializer<'de> {
@@ -142,3 +511,91 @@ impl<'de> de::IntoDeserializer<'de, super::Error> for BorrowedDeserializer<'de>
         self
     }
 }
+
+pub struct UnitVariant;
+
+impl<'de> de::VariantAccess<'de> for UnitVariant {
+    type Error = super::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, _: T) -> Result<T::Value, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"newtype variant",
+        ))
+    }
+
+    fn tuple_variant<V>(self, _: usize, _: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"tuple variant",
+        ))
+    }
+
+    fn struct_variant<V>(self, _: &'static [&'static str], _: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"struct variant",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use polars_core::datatypes::AnyValue;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_float16() {
+        let v = polars_utils::float16::pf16::from(1.5_f32);
+        assert_eq!(
+            f32::deserialize(super::Deserializer::new(AnyValue::Float16(v))).unwrap(),
+            1.5,
+        );
+        assert_eq!(
+            crate::Value::deserialize(super::BorrowedDeserializer::new(AnyValue::Float16(v)))
+                .unwrap(),
+            crate::Value::Float32(1.5),
+        );
+    }
+
+    #[test]
+    fn test_integer_narrowing() {
+        assert_eq!(
+            i64::deserialize(super::Deserializer::new(AnyValue::Int128(-42))).unwrap(),
+            -42,
+        );
+        assert_eq!(
+            u8::deserialize(super::Deserializer::new(AnyValue::UInt128(255))).unwrap(),
+            255,
+        );
+        assert_eq!(
+            i128::deserialize(super::Deserializer::new(AnyValue::UInt128(1 << 100))).unwrap(),
+            1 << 100,
+        );
+        assert_eq!(
+            u128::deserialize(super::Deserializer::new(AnyValue::Int64(7))).unwrap(),
+            7,
+        );
+
+        let e =
+            i64::deserialize(super::Deserializer::new(AnyValue::Int128(i128::MAX))).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            format!("integer {} is out of range for i64", i128::MAX),
+        );
+        assert!(u128::deserialize(super::Deserializer::new(AnyValue::Int128(-1))).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use ndarray::{Array2, Order, ShapeBuilder};
+use polars_core::datatypes::AnyValue;
+use polars_core::frame::DataFrame;
+use polars_core::prelude::PlSmallStr;
+use polars_core::series::Series;
+use serde::de;
+use serde::de::Deserialize;
+
+/// Reads every column of `value` into a `height × width` array laid out in `order`.
+///
+/// Values are converted like `T::deserialize` would, so integer columns can be read as `f64`,
+/// and values that `T` cannot be read from are errors.
+/// Nulls are replaced by `fill`, or rejected if it is `None`.
+/// Select the columns beforehand (e.g. with `DataFrame::select`) to read only some of them.
+pub fn to_array2<T>(
+    value: &DataFrame,
+    order: Order,
+    fill: Option<T>,
+) -> Result<Array2<T>, super::Error>
+where
+    T: de::DeserializeOwned + Clone,
+{
+    let (height, width) = value.shape();
+    let mut columns = value
+        .columns()
+        .iter()
+        .map(|column| (column.name(), super::cells(column, Series::iter)))
+        .collect::<Vec<_>>();
+    let mut values = Vec::with_capacity(height * width);
+    if order.is_column_major() {
+        for column in &mut columns {
+            for _ in 0..height {
+                values.push(next(column, fill.as_ref())?);
+            }
+        }
+    } else {
+        for _ in 0..height {
+            for column in &mut columns {
+                values.push(next(column, fill.as_ref())?);
+            }
+        }
+    }
+    Array2::from_shape_vec((height, width).set_f(order.is_column_major()), values)
+        .map_err(de::Error::custom)
+}
+
+fn next<'a, T>(
+    (name, iter): &mut (&PlSmallStr, impl Iterator<Item = AnyValue<'a>>),
+    fill: Option<&T>,
+) -> Result<T, super::Error>
+where
+    T: de::DeserializeOwned + Clone,
+{
+    match Option::<T>::deserialize(super::AnyValueDeserializer::new(
+        iter.next().unwrap_or_default(),
+    ))? {
+        Some(v) => Ok(v),
+        None => fill
+            .cloned()
+            .ok_or_else(|| de::Error::custom(format_args!("null value in column {name}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{Order, array};
+    use polars_core::frame::DataFrame;
+    use polars_core::frame::column::Column;
+
+    #[test]
+    fn test_to_array2() {
+        let s1 = Column::new("a".into(), [1_i64, 2, 3]);
+        let s2 = Column::new("b".into(), [Some(0.5_f64), None, Some(1.5)]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        for order in [Order::RowMajor, Order::ColumnMajor] {
+            let array = super::to_array2(&df, order, Some(0.0)).unwrap();
+            assert_eq!(array, array![[1.0, 0.5], [2.0, 0.0], [3.0, 1.5]]);
+            assert_eq!(array.is_standard_layout(), order == Order::RowMajor);
+        }
+
+        assert!(super::to_array2::<f64>(&df, Order::RowMajor, None).is_err());
+    }
+
+    #[test]
+    fn test_chunked() {
+        let mut df =
+            DataFrame::new_infer_height(vec![Column::new("a".into(), [1_i64, 2])]).unwrap();
+        df.vstack_mut(
+            &DataFrame::new_infer_height(vec![Column::new("a".into(), [3_i64])]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(df.first_col_n_chunks(), 2);
+
+        let array = super::to_array2::<i64>(&df, Order::RowMajor, None).unwrap();
+        assert_eq!(array, array![[1], [2], [3]]);
+    }
+
+    #[cfg(feature = "dtype-categorical")]
+    #[test]
+    fn test_categorical() {
+        use polars_core::datatypes::{Categories, DataType};
+
+        let df = DataFrame::new_infer_height(vec![
+            Column::new("a".into(), ["x", "y"])
+                .cast(&DataType::from_categories(Categories::global()))
+                .unwrap(),
+        ])
+        .unwrap();
+
+        let array = super::to_array2::<String>(&df, Order::RowMajor, None).unwrap();
+        assert_eq!(array, array![["x".to_owned()], ["y".to_owned()]]);
+        assert!(super::to_array2::<f64>(&df, Order::RowMajor, None).is_err());
+    }
+}
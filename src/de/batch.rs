@@ -0,0 +1,124 @@
+use polars_core::frame::DataFrame;
+use polars_core::prelude::{SchemaRef, polars_err};
+use serde::de;
+use std::vec;
+
+/// Deserializes the rows of a sequence of batches, e.g. from an IPC stream, one batch at a time.
+///
+/// Every batch must have the same schema as the first one. The iterator ends after the first
+/// error.
+pub struct BatchRowIter<I, T> {
+    batches: Option<I>,
+    schema: Option<SchemaRef>,
+    rows: vec::IntoIter<T>,
+}
+
+impl<I, T> BatchRowIter<I, T> {
+    pub fn new<B>(batches: B) -> Self
+    where
+        B: IntoIterator<IntoIter = I>,
+    {
+        Self {
+            batches: Some(batches.into_iter()),
+            schema: None,
+            rows: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<I, T> Iterator for BatchRowIter<I, T>
+where
+    I: Iterator<Item = DataFrame>,
+    T: de::DeserializeOwned,
+{
+    type Item = Result<T, super::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(row) = self.rows.next() {
+                return Some(Ok(row));
+            }
+            let batch = self.batches.as_mut()?.next()?;
+            match &self.schema {
+                Some(schema) if schema != batch.schema() => {
+                    self.batches = None;
+                    return Some(Err(polars_err!(
+                        SchemaMismatch: "batch schema {:?} differs from {:?}",
+                        batch.schema(),
+                        schema,
+                    )
+                    .into()));
+                }
+                Some(_) => {}
+                None => self.schema = Some(batch.schema().clone()),
+            }
+            match de::Deserialize::deserialize(super::DataFrameDeserializer::rows(&batch)) {
+                Ok(rows) => self.rows = Vec::into_iter(rows),
+                Err(e) => {
+                    self.batches = None;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use polars_core::frame::DataFrame;
+    use polars_core::frame::column::Column;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Row {
+        a: u32,
+        b: String,
+    }
+
+    fn batch(a: &[u32], b: &[&str]) -> DataFrame {
+        DataFrame::new_infer_height(vec![Column::new("a".into(), a), Column::new("b".into(), b)])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_batch_row_iter() {
+        let batches = [
+            batch(&[1, 2], &["x", "y"]),
+            batch(&[], &[]),
+            batch(&[3], &["z"]),
+        ];
+        let rows = super::BatchRowIter::new(batches)
+            .collect::<Result<Vec<Row>, _>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            [
+                Row {
+                    a: 1,
+                    b: "x".to_owned(),
+                },
+                Row {
+                    a: 2,
+                    b: "y".to_owned(),
+                },
+                Row {
+                    a: 3,
+                    b: "z".to_owned(),
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_batch_row_iter_schema_mismatch() {
+        let other = DataFrame::new_infer_height(vec![Column::new("a".into(), [3_u32])]).unwrap();
+        let mut rows =
+            super::BatchRowIter::<_, Row>::new([batch(&[1], &["x"]), other, batch(&[2], &["y"])]);
+        assert!(rows.next().unwrap().is_ok());
+        assert!(matches!(
+            rows.next(),
+            Some(Err(crate::de::Error::Polars(_))),
+        ));
+        assert!(rows.next().is_none());
+    }
+}
@@ -1,16 +1,169 @@
+use polars_core::datatypes::AnyValue;
+#[cfg(feature = "dtype-categorical")]
+use polars_core::datatypes::CatSize;
 use polars_core::frame::DataFrame;
+use polars_core::frame::column::Column;
+#[cfg(feature = "rows")]
+use polars_core::frame::row::Row;
+use polars_core::series::Series;
+#[cfg(feature = "rows")]
+use polars_core::series::SeriesIter;
 use serde::de;
+use std::borrow::Cow;
+use std::ops::Range;
 
-pub struct Deserializer<'a>(&'a DataFrame, Order);
+pub struct Deserializer<'a>(&'a DataFrame, Order, Options<'a>);
 
 impl<'a> Deserializer<'a> {
+    /// Visits the columns as a map of column names to values, in the order they appear in the frame.
     pub fn columns(value: &'a DataFrame) -> Self {
-        Self(value, Order::Column)
+        Self(value, Order::Column, Options::default())
     }
 
     #[cfg(feature = "rows")]
     pub fn rows(value: &'a DataFrame) -> Self {
-        Self(value, Order::Row)
+        Self(value, Order::Row, Options::default())
+    }
+
+    /// Deserializes only the row at `index`.
+    #[cfg(feature = "rows")]
+    pub fn row(value: &'a DataFrame, index: usize) -> Self {
+        Self(value, Order::Single(Some(index)), Options::default())
+    }
+
+    /// Deserializes the only row of a frame, failing if it does not have exactly one row.
+    #[cfg(feature = "rows")]
+    pub fn single_row(value: &'a DataFrame) -> Self {
+        Self(value, Order::Single(None), Options::default())
+    }
+
+    /// Visits a map from the values of the `keys` columns to the rest of each row.
+    #[cfg(feature = "rows")]
+    pub fn keyed(value: &'a DataFrame, keys: &'a [&'a str]) -> Self {
+        Self(
+            value,
+            Order::Keyed,
+            Options {
+                keys,
+                ..Options::default()
+            },
+        )
+    }
+
+    /// Visits one map per group with the `keys` columns and the remaining rows under `items`.
+    #[cfg(feature = "group-by")]
+    pub fn grouped(value: &'a DataFrame, keys: &'a [&'a str], items: &'a str) -> Self {
+        Self(
+            value,
+            Order::Grouped,
+            Options {
+                keys,
+                items,
+                ..Options::default()
+            },
+        )
+    }
+
+    /// Sets how rows with the same key are handled in [`keyed`](Self::keyed) mode.
+    #[cfg(feature = "rows")]
+    pub fn with_duplicate_keys(mut self, duplicate_keys: super::DuplicateKeys) -> Self {
+        self.2.duplicate_keys = duplicate_keys;
+        self
+    }
+
+    /// Groups columns such as `address.city` and `address.zip` into a nested `address` map in row order.
+    #[cfg(feature = "rows")]
+    pub fn with_separator(mut self, separator: &'a str) -> Self {
+        self.2.separator = Some(separator);
+        self
+    }
+
+    /// Rewrites column names before they are matched against field names.
+    pub fn with_key_normalizer(mut self, key_normalizer: super::KeyNormalizer<'a>) -> Self {
+        self.2.key_normalizer = Some(key_normalizer);
+        self
+    }
+
+    /// Sets how categorical values are presented.
+    pub fn with_categorical(mut self, categorical: super::CategoricalRepr) -> Self {
+        self.2.categorical = categorical;
+        self
+    }
+
+    /// Same as `Vec::<T>::deserialize(self)`, but the frame is split into one slice per rayon thread.
+    ///
+    /// Fails unless the deserializer was created with [`rows`](Self::rows).
+    #[cfg(all(feature = "parallel", feature = "rows"))]
+    pub fn par_rows<T>(self) -> Result<Vec<T>, super::Error>
+    where
+        T: de::DeserializeOwned + Send,
+    {
+        use rayon::prelude::*;
+
+        if !matches!(self.1, Order::Row) {
+            return Err(de::Error::custom(
+                "`par_rows` needs a deserializer in row order",
+            ));
+        }
+        let len = self
+            .0
+            .height()
+            .div_ceil(rayon::current_num_threads())
+            .max(1);
+        let chunks = (0..self.0.height().div_ceil(len))
+            .into_par_iter()
+            .map(|i| {
+                let slice = self.0.slice((i * len) as i64, len);
+                de::Deserialize::deserialize(Deserializer(&slice, Order::Row, self.2))
+            })
+            .collect::<Result<Vec<Vec<T>>, _>>()?;
+        Ok(chunks.into_iter().flatten().collect())
+    }
+
+    /// Deserializes a map or tuple of columns like `T::deserialize(self)`, but each column on its
+    /// own rayon thread.
+    ///
+    /// Fails unless the deserializer was created with [`columns`](Self::columns).
+    #[cfg(feature = "parallel")]
+    pub fn par_columns<'de, T>(self) -> Result<T, super::Error>
+    where
+        T: super::ParColumns<'de>,
+    {
+        if !matches!(self.1, Order::Column) {
+            return Err(de::Error::custom(
+                "`par_columns` needs a deserializer in column order",
+            ));
+        }
+        T::par_columns(&self)
+    }
+
+    #[cfg(feature = "parallel")]
+    pub(super) fn width(&self) -> usize {
+        self.0.width()
+    }
+
+    /// Deserializes the name of the column at `index` as a map key.
+    #[cfg(feature = "parallel")]
+    pub(super) fn key<'de, K>(&self, index: usize) -> Result<K, super::Error>
+    where
+        K: de::Deserialize<'de>,
+    {
+        K::deserialize(super::key::Deserializer::new(
+            self.2.key(self.0.columns()[index].name(), &[]),
+        ))
+    }
+
+    /// Deserializes the values of the column at `index` as in `columns` mode.
+    #[cfg(feature = "parallel")]
+    pub(super) fn column<'de, C>(&self, index: usize) -> Result<C, super::Error>
+    where
+        C: de::Deserialize<'de>,
+    {
+        C::deserialize(ColumnDeserializer(
+            cells(&self.0.columns()[index], Series::iter).map(|value| {
+                super::AnyValueDeserializer::new(value).with_categorical(self.2.categorical)
+            }),
+        ))
     }
 }
 
@@ -18,6 +171,18 @@ impl<'de, 'a> de::Deserializer<'de> for Deserializer<'a> {
     type Error = super::Error;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_struct("", &[], visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
@@ -25,29 +190,98 @@ impl<'de, 'a> de::Deserializer<'de> for Deserializer<'a> {
             Order::Column => visitor.visit_map(de::value::MapDeserializer::new(
                 self.0.columns().iter().map(|column| {
                     (
-                        de::value::StrDeserializer::new(column.name()),
-                        de::value::SeqDeserializer::new(
-                            column.phys_iter().map(super::AnyValueDeserializer::new),
-                        ),
+                        super::key::Deserializer::new(self.2.key(column.name(), fields)),
+                        ColumnDeserializer(cells(column, Series::iter).map(|value| {
+                            super::AnyValueDeserializer::new(value)
+                                .with_categorical(self.2.categorical)
+                        })),
                     )
                 }),
             )),
             #[cfg(feature = "rows")]
+            Order::Row if self.2.separator.is_some() || self.2.key_normalizer.is_some() => {
+                let tree = self.2.tree(self.0);
+                visitor.visit_seq(de::value::SeqDeserializer::new(Rows::new(self.0).map(
+                    |row| {
+                        super::nested::Deserializer::new(&tree, row)
+                            .with_categorical(self.2.categorical)
+                    },
+                )))
+            }
+            #[cfg(feature = "rows")]
             Order::Row => visitor.visit_seq(de::value::SeqDeserializer::new(
-                (0..self.0.height()).map(|i| {
-                    ResultDeserializer(
-                        self.0
-                            .get_row(i)
-                            .map(|row| {
-                                super::RowDeserializer::new(
-                                    self.0.columns().iter().map(|column| column.name().as_str()),
-                                    row,
-                                )
-                            })
-                            .map_err(super::Error::from),
+                Rows::new(self.0).map(|row| {
+                    super::RowDeserializer::new(
+                        self.0.columns().iter().map(|column| column.name().as_str()),
+                        row,
                     )
+                    .with_categorical(self.2.categorical)
                 }),
             )),
+            #[cfg(feature = "rows")]
+            Order::Keyed => {
+                let (names, rows) =
+                    super::keyed::split(self.0, self.2.keys, self.2.duplicate_keys)?;
+                let tree = self.2.separator.map(|separator| {
+                    super::nested::Tree::new(
+                        names.iter().copied(),
+                        Some(separator),
+                        self.2.key_normalizer,
+                    )
+                });
+                visitor.visit_map(super::keyed::Access::new(
+                    &names,
+                    rows,
+                    tree.as_ref(),
+                    self.2.key_normalizer,
+                    self.2.categorical,
+                ))
+            }
+            #[cfg(feature = "group-by")]
+            Order::Grouped => {
+                let (names, groups) = super::grouped::split(self.0, self.2.keys)?;
+                let tree = self.2.separator.map(|separator| {
+                    super::nested::Tree::new(
+                        names.iter().copied(),
+                        Some(separator),
+                        self.2.key_normalizer,
+                    )
+                });
+                let layout = super::grouped::Layout {
+                    keys: self.2.keys,
+                    items: self.2.items,
+                    names: &names,
+                    tree: tree.as_ref(),
+                    key_normalizer: self.2.key_normalizer,
+                    categorical: self.2.categorical,
+                };
+                visitor.visit_seq(de::value::SeqDeserializer::new(
+                    groups
+                        .into_iter()
+                        .map(|group| super::grouped::Deserializer::new(layout, group)),
+                ))
+            }
+            #[cfg(feature = "rows")]
+            Order::Single(None) if self.0.height() != 1 => Err(de::Error::invalid_length(
+                self.0.height(),
+                &"a frame with exactly one row",
+            )),
+            #[cfg(feature = "rows")]
+            Order::Single(i) => {
+                let row = self.0.get_row(i.unwrap_or(0))?;
+                if self.2.separator.is_some() || self.2.key_normalizer.is_some() {
+                    super::nested::Deserializer::new(&self.2.tree(self.0), row)
+                        .with_categorical(self.2.categorical)
+                        .deserialize_struct("", fields, visitor)
+                } else {
+                    super::RowDeserializer::new(
+                        self.0.columns().iter().map(|column| column.name().as_str()),
+                        row,
+                    )
+                    .with_categorical(self.2.categorical)
+                    .deserialize_struct("", fields, visitor)
+                }
+            }
         }
     }
 
@@ -58,7 +292,7 @@ impl<'de, 'a> de::Deserializer<'de> for Deserializer<'a> {
         self.deserialize_any(visitor)
     }
 
-    deserialize_delegate!();
+    deserialize_delegate!(@ except deserialize_struct);
 }
 
 impl<'de, 'a> de::IntoDeserializer<'de, super::Error> for Deserializer<'a> {
@@ -69,16 +303,82 @@ impl<'de, 'a> de::IntoDeserializer<'de, super::Error> for Deserializer<'a> {
     }
 }
 
-pub struct BorrowedDeserializer<'de>(&'de DataFrame, Order);
+pub struct BorrowedDeserializer<'de>(&'de DataFrame, Order, Options<'de>);
 
 impl<'de> BorrowedDeserializer<'de> {
+    /// Visits the columns as a map of column names to values, in the order they appear in the frame.
     pub fn columns(value: &'de DataFrame) -> Self {
-        Self(value, Order::Column)
+        Self(value, Order::Column, Options::default())
     }
 
     #[cfg(feature = "rows")]
     pub fn rows(value: &'de DataFrame) -> Self {
-        Self(value, Order::Row)
+        Self(value, Order::Row, Options::default())
+    }
+
+    /// Deserializes only the row at `index`.
+    #[cfg(feature = "rows")]
+    pub fn row(value: &'de DataFrame, index: usize) -> Self {
+        Self(value, Order::Single(Some(index)), Options::default())
+    }
+
+    /// Deserializes the only row of a frame, failing if it does not have exactly one row.
+    #[cfg(feature = "rows")]
+    pub fn single_row(value: &'de DataFrame) -> Self {
+        Self(value, Order::Single(None), Options::default())
+    }
+
+    /// Visits a map from the values of the `keys` columns to the rest of each row.
+    #[cfg(feature = "rows")]
+    pub fn keyed(value: &'de DataFrame, keys: &'de [&'de str]) -> Self {
+        Self(
+            value,
+            Order::Keyed,
+            Options {
+                keys,
+                ..Options::default()
+            },
+        )
+    }
+
+    /// Visits one map per group with the `keys` columns and the remaining rows under `items`.
+    #[cfg(feature = "group-by")]
+    pub fn grouped(value: &'de DataFrame, keys: &'de [&'de str], items: &'de str) -> Self {
+        Self(
+            value,
+            Order::Grouped,
+            Options {
+                keys,
+                items,
+                ..Options::default()
+            },
+        )
+    }
+
+    /// Sets how rows with the same key are handled in [`keyed`](Self::keyed) mode.
+    #[cfg(feature = "rows")]
+    pub fn with_duplicate_keys(mut self, duplicate_keys: super::DuplicateKeys) -> Self {
+        self.2.duplicate_keys = duplicate_keys;
+        self
+    }
+
+    /// Groups columns such as `address.city` and `address.zip` into a nested `address` map in row order.
+    #[cfg(feature = "rows")]
+    pub fn with_separator(mut self, separator: &'de str) -> Self {
+        self.2.separator = Some(separator);
+        self
+    }
+
+    /// Rewrites column names before they are matched against field names.
+    pub fn with_key_normalizer(mut self, key_normalizer: super::KeyNormalizer<'de>) -> Self {
+        self.2.key_normalizer = Some(key_normalizer);
+        self
+    }
+
+    /// Sets how categorical values are presented.
+    pub fn with_categorical(mut self, categorical: super::CategoricalRepr) -> Self {
+        self.2.categorical = categorical;
+        self
     }
 }
 
@@ -86,6 +386,18 @@ impl<'de> de::Deserializer<'de> for BorrowedDeserializer<'de> {
     type Error = super::Error;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_struct("", &[], visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
@@ -93,31 +405,98 @@ impl<'de> de::Deserializer<'de> for BorrowedDeserializer<'de> {
             Order::Column => visitor.visit_map(de::value::MapDeserializer::new(
                 self.0.columns().iter().map(|column| {
                     (
-                        de::value::BorrowedStrDeserializer::new(column.name()),
-                        de::value::SeqDeserializer::new(
-                            column
-                                .phys_iter()
-                                .map(super::BorrowedAnyValueDeserializer::new),
-                        ),
+                        super::key::BorrowedDeserializer::new(self.2.key(column.name(), fields)),
+                        ColumnDeserializer(cells(column, Series::iter).map(|value| {
+                            super::BorrowedAnyValueDeserializer::new(value)
+                                .with_categorical(self.2.categorical)
+                        })),
                     )
                 }),
             )),
             #[cfg(feature = "rows")]
+            Order::Row if self.2.separator.is_some() || self.2.key_normalizer.is_some() => {
+                let tree = self.2.tree(self.0);
+                visitor.visit_seq(de::value::SeqDeserializer::new(Rows::new(self.0).map(
+                    |row| {
+                        super::nested::BorrowedDeserializer::new(&tree, row)
+                            .with_categorical(self.2.categorical)
+                    },
+                )))
+            }
+            #[cfg(feature = "rows")]
             Order::Row => visitor.visit_seq(de::value::SeqDeserializer::new(
-                (0..self.0.height()).map(|i| {
-                    ResultDeserializer(
-                        self.0
-                            .get_row(i)
-                            .map(|row| {
-                                super::BorrowedRowDeserializer::new(
-                                    self.0.columns().iter().map(|column| column.name().as_str()),
-                                    row,
-                                )
-                            })
-                            .map_err(super::Error::from),
+                Rows::new(self.0).map(|row| {
+                    super::BorrowedRowDeserializer::new(
+                        self.0.columns().iter().map(|column| column.name().as_str()),
+                        row,
                     )
+                    .with_categorical(self.2.categorical)
                 }),
             )),
+            #[cfg(feature = "rows")]
+            Order::Keyed => {
+                let (names, rows) =
+                    super::keyed::split(self.0, self.2.keys, self.2.duplicate_keys)?;
+                let tree = self.2.separator.map(|separator| {
+                    super::nested::Tree::new(
+                        names.iter().copied(),
+                        Some(separator),
+                        self.2.key_normalizer,
+                    )
+                });
+                visitor.visit_map(super::keyed::BorrowedAccess::new(
+                    &names,
+                    rows,
+                    tree.as_ref(),
+                    self.2.key_normalizer,
+                    self.2.categorical,
+                ))
+            }
+            #[cfg(feature = "group-by")]
+            Order::Grouped => {
+                let (names, groups) = super::grouped::split(self.0, self.2.keys)?;
+                let tree = self.2.separator.map(|separator| {
+                    super::nested::Tree::new(
+                        names.iter().copied(),
+                        Some(separator),
+                        self.2.key_normalizer,
+                    )
+                });
+                let layout = super::grouped::Layout {
+                    keys: self.2.keys,
+                    items: self.2.items,
+                    names: &names,
+                    tree: tree.as_ref(),
+                    key_normalizer: self.2.key_normalizer,
+                    categorical: self.2.categorical,
+                };
+                visitor.visit_seq(de::value::SeqDeserializer::new(
+                    groups
+                        .into_iter()
+                        .map(|group| super::grouped::BorrowedDeserializer::new(layout, group)),
+                ))
+            }
+            #[cfg(feature = "rows")]
+            Order::Single(None) if self.0.height() != 1 => Err(de::Error::invalid_length(
+                self.0.height(),
+                &"a frame with exactly one row",
+            )),
+            #[cfg(feature = "rows")]
+            Order::Single(i) => {
+                let row = self.0.get_row(i.unwrap_or(0))?;
+                if self.2.separator.is_some() || self.2.key_normalizer.is_some() {
+                    super::nested::BorrowedDeserializer::new(&self.2.tree(self.0), row)
+                        .with_categorical(self.2.categorical)
+                        .deserialize_struct("", fields, visitor)
+                } else {
+                    super::BorrowedRowDeserializer::new(
+                        self.0.columns().iter().map(|column| column.name().as_str()),
+                        row,
+                    )
+                    .with_categorical(self.2.categorical)
+                    .deserialize_struct("", fields, visitor)
+                }
+            }
         }
     }
 
@@ -128,7 +507,7 @@ impl<'de> de::Deserializer<'de> for BorrowedDeserializer<'de> {
         self.deserialize_any(visitor)
     }
 
-    deserialize_delegate!();
+    deserialize_delegate!(@ except deserialize_struct);
 }
 
 impl<'de> de::IntoDeserializer<'de, super::Error> for BorrowedDeserializer<'de> {
@@ -139,19 +518,82 @@ impl<'de> de::IntoDeserializer<'de, super::Error> for BorrowedDeserializer<'de>
     }
 }
 
+/// Deserializes each row of `value` as a `T`, borrowing strings and bytes from the frame.
+#[cfg(feature = "rows")]
+pub fn from_rows<'de, T>(value: &'de DataFrame) -> Result<Vec<T>, super::Error>
+where
+    T: de::Deserialize<'de>,
+{
+    de::Deserialize::deserialize(BorrowedDeserializer::rows(value))
+}
+
+/// Deserializes `value` as a `T` whose fields are columns, borrowing strings and bytes from the frame.
+pub fn from_columns<'de, T>(value: &'de DataFrame) -> Result<T, super::Error>
+where
+    T: de::Deserialize<'de>,
+{
+    T::deserialize(BorrowedDeserializer::columns(value))
+}
+
+/// Returns the labels of a `Categorical` or `Enum` column indexed by code.
+#[cfg(feature = "dtype-categorical")]
+pub fn categories(column: &Column) -> Result<Vec<Option<&str>>, super::Error> {
+    let mapping = column.dtype().cat_mapping()?;
+    Ok((0..mapping.num_cats_upper_bound())
+        .map(|cat| mapping.cat_to_str(cat as CatSize))
+        .collect())
+}
+
+#[derive(Clone, Copy, Default)]
+struct Options<'a> {
+    #[cfg(feature = "rows")]
+    separator: Option<&'a str>,
+    #[cfg(feature = "rows")]
+    keys: &'a [&'a str],
+    #[cfg(feature = "rows")]
+    duplicate_keys: super::DuplicateKeys,
+    #[cfg(feature = "group-by")]
+    items: &'a str,
+    key_normalizer: Option<super::KeyNormalizer<'a>>,
+    categorical: super::CategoricalRepr,
+}
+
+impl<'a> Options<'a> {
+    fn key<'b>(&self, name: &'b str, fields: &'static [&'static str]) -> Cow<'b, str> {
+        super::key::normalize(self.key_normalizer, name, fields)
+    }
+
+    #[cfg(feature = "rows")]
+    fn tree<'b>(&self, value: &'b DataFrame) -> super::nested::Tree<'b>
+    where
+        'a: 'b,
+    {
+        super::nested::Tree::new(
+            value.columns().iter().map(|column| column.name().as_str()),
+            self.separator,
+            self.key_normalizer,
+        )
+    }
+}
+
 enum Order {
     Column,
     #[cfg(feature = "rows")]
     Row,
+    #[cfg(feature = "rows")]
+    Single(Option<usize>),
+    #[cfg(feature = "rows")]
+    Keyed,
+    #[cfg(feature = "group-by")]
+    Grouped,
 }
 
-#[cfg(feature = "rows")]
-struct ResultDeserializer<T>(Result<T, super::Error>);
+struct ColumnDeserializer<I>(I);
 
-#[cfg(feature = "rows")]
-impl<'de, T> de::Deserializer<'de> for ResultDeserializer<T>
+impl<'de, I> de::Deserializer<'de> for ColumnDeserializer<I>
 where
-    T: de::Deserializer<'de, Error = super::Error>,
+    I: Iterator,
+    I::Item: de::IntoDeserializer<'de, super::Error>,
 {
     type Error = super::Error;
 
@@ -159,23 +601,26 @@ where
     where
         V: de::Visitor<'de>,
     {
-        self.0?.deserialize_any(visitor)
+        let mut seq = de::value::SeqDeserializer::new(self.0);
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        self.deserialize_any(visitor)
+        visitor.visit_some(self)
     }
 
     deserialize_delegate!();
 }
 
-#[cfg(feature = "rows")]
-impl<'de, T> de::IntoDeserializer<'de, super::Error> for ResultDeserializer<T>
+impl<'de, I> de::IntoDeserializer<'de, super::Error> for ColumnDeserializer<I>
 where
-    T: de::Deserializer<'de, Error = super::Error>,
+    I: Iterator,
+    I::Item: de::IntoDeserializer<'de, super::Error>,
 {
     type Deserializer = Self;
 
@@ -184,11 +629,84 @@ where
     }
 }
 
+// `Object` values are read one by one since iterating them requires polars' object registry
+pub(crate) fn cells<'a, I>(column: &'a Column, iter: impl FnOnce(&'a Series) -> I) -> Cells<'a, I>
+where
+    I: Iterator<Item = AnyValue<'a>>,
+{
+    let series = column.as_materialized_series();
+    if series.dtype().is_object() {
+        Cells::Object(series, 0..series.len())
+    } else {
+        Cells::Iter(iter(series))
+    }
+}
+
+pub(crate) enum Cells<'a, I> {
+    Iter(I),
+    Object(&'a Series, Range<usize>),
+}
+
+impl<'a, I> Iterator for Cells<'a, I>
+where
+    I: Iterator<Item = AnyValue<'a>>,
+{
+    type Item = AnyValue<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Iter(iter) => iter.next(),
+            Self::Object(series, range) => range.next().map(|i| series.get(i).unwrap_or_default()),
+        }
+    }
+}
+
+#[cfg(feature = "rows")]
+pub(super) struct Rows<'a>(Vec<Cells<'a, SeriesIter<'a>>>, usize);
+
+#[cfg(feature = "rows")]
+impl<'a> Rows<'a> {
+    pub(super) fn new(value: &'a DataFrame) -> Self {
+        Self(
+            value
+                .columns()
+                .iter()
+                .map(|column| cells(column, Series::iter))
+                .collect(),
+            value.height(),
+        )
+    }
+}
+
+#[cfg(feature = "rows")]
+impl<'a> Iterator for Rows<'a> {
+    type Item = Row<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.1 = self.1.checked_sub(1)?;
+        Some(Row(self
+            .0
+            .iter_mut()
+            .map(|column| column.next().unwrap_or_default())
+            .collect()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.1, Some(self.1))
+    }
+}
+
+#[cfg(feature = "rows")]
+impl ExactSizeIterator for Rows<'_> {}
+
 #[cfg(test)]
 mod tests {
+    use indexmap::IndexMap;
+    use polars_core::datatypes::DataType;
     use polars_core::frame::DataFrame;
     use polars_core::frame::column::Column;
     use serde::Deserialize;
+    use std::collections::HashMap;
 
     #[test]
     fn test_columns() {
@@ -214,34 +732,1017 @@ mod tests {
         );
     }
 
-    #[cfg(feature = "rows")]
     #[test]
-    fn test_rows() {
+    fn test_columns_binary_and_string() {
         #[derive(Debug, PartialEq, Deserialize)]
-        struct Row<'a> {
-            #[serde(rename = "Ocean")]
-            ocean: &'a str,
-            #[serde(rename = "Area (km²)")]
-            area: u64,
+        struct Columns<'a> {
+            #[serde(borrow)]
+            name: Vec<&'a str>,
+            payload: Vec<Vec<u8>>,
+            raw: Vec<String>,
+        }
+
+        let s1 = Column::new("name".into(), [b"Atlantic".as_slice(), b"Indian"]);
+        let s2 = Column::new("payload".into(), ["abc", "de"]);
+        let s3 = Column::new("raw".into(), [b"x".as_slice(), b"yz"])
+            .cast(&DataType::BinaryOffset)
+            .unwrap();
+        let df = DataFrame::new_infer_height(vec![s1, s2, s3]).unwrap();
+
+        assert_eq!(
+            Columns::deserialize(super::BorrowedDeserializer::columns(&df)).unwrap(),
+            Columns {
+                name: vec!["Atlantic", "Indian"],
+                payload: vec![b"abc".to_vec(), b"de".to_vec()],
+                raw: vec!["x".to_owned(), "yz".to_owned()],
+            },
+        );
+
+        let s1 = Column::new("name".into(), [b"\xff".as_slice()]);
+        let df = DataFrame::new_infer_height(vec![s1]).unwrap();
+        assert!(
+            HashMap::<String, Vec<String>>::deserialize(super::Deserializer::columns(&df)).is_err()
+        );
+    }
+
+    #[test]
+    fn test_columns_char() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Columns {
+            letter: Vec<char>,
+            code: Vec<Option<char>>,
+        }
+
+        let s1 = Column::new("letter".into(), ["a", "é"]);
+        let s2 = Column::new("code".into(), [Some(0x1f600_u32), None]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        assert_eq!(
+            Columns::deserialize(super::BorrowedDeserializer::columns(&df)).unwrap(),
+            Columns {
+                letter: vec!['a', 'é'],
+                code: vec![Some('😀'), None],
+            },
+        );
+
+        let s1 = Column::new("letter".into(), ["ab"]);
+        let df = DataFrame::new_infer_height(vec![s1]).unwrap();
+        let e = HashMap::<String, Vec<char>>::deserialize(super::Deserializer::columns(&df))
+            .unwrap_err();
+        assert!(e.to_string().contains("\"ab\""), "{e}");
+
+        // "é" as e + U+0301 is one grapheme but two code points
+        let s1 = Column::new("letter".into(), ["e\u{301}"]);
+        let df = DataFrame::new_infer_height(vec![s1]).unwrap();
+        let e = HashMap::<String, Vec<char>>::deserialize(super::Deserializer::columns(&df))
+            .unwrap_err();
+        assert!(e.to_string().contains("multi-code-point grapheme"), "{e}");
+
+        let s1 = Column::new("letter".into(), [0xd800_u32]);
+        let df = DataFrame::new_infer_height(vec![s1]).unwrap();
+        let e = HashMap::<String, Vec<char>>::deserialize(super::Deserializer::columns(&df))
+            .unwrap_err();
+        assert!(e.to_string().contains("55296"), "{e}");
+    }
+
+    #[test]
+    fn test_columns_newtype() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Columns {
+            id: Ids,
+            depth: Vec<Meters>,
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Ids(Vec<u32>);
+
+        let s1 = Column::new("id".into(), [1_u32, 2]);
+        let s2 = Column::new("depth".into(), [8_486.0, 7_450.0]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        assert_eq!(
+            Columns::deserialize(super::Deserializer::columns(&df)).unwrap(),
+            Columns {
+                id: Ids(vec![1, 2]),
+                depth: vec![Meters(8_486.0), Meters(7_450.0)],
+            },
+        );
+    }
+
+    #[cfg(all(feature = "dtype-i128", feature = "dtype-u128"))]
+    #[test]
+    fn test_columns_128() {
+        #[derive(Debug, PartialEq, serde::Serialize, Deserialize)]
+        struct Row {
+            signed: i128,
+            unsigned: u128,
+        }
+
+        let rows = [
+            Row {
+                signed: i128::MIN,
+                unsigned: u128::MAX,
+            },
+            Row {
+                signed: 1,
+                unsigned: 2,
+            },
+        ];
+        let df = crate::to_data_frame(&rows).unwrap();
+        assert_eq!(df.column("signed").unwrap().dtype(), &DataType::Int128);
+        assert_eq!(df.column("unsigned").unwrap().dtype(), &DataType::UInt128);
+
+        let columns = HashMap::<String, Vec<i128>>::deserialize(super::Deserializer::columns(
+            &df.head(Some(1)),
+        ))
+        .unwrap_err();
+        assert!(
+            columns.to_string().contains("out of range for i128"),
+            "{columns}"
+        );
+
+        #[cfg(feature = "rows")]
+        assert_eq!(crate::from_rows::<Row>(&df).unwrap(), rows);
+    }
+
+    #[cfg(all(
+        feature = "dtype-f16",
+        feature = "dtype-i8",
+        feature = "dtype-i16",
+        feature = "dtype-u8",
+        feature = "dtype-u16",
+    ))]
+    #[test]
+    fn test_columns_small() {
+        #[derive(Debug, PartialEq, serde::Serialize, Deserialize)]
+        struct Row {
+            a: i8,
+            b: i16,
+            c: u8,
+            d: u16,
         }
 
+        let rows = [Row {
+            a: -1,
+            b: -300,
+            c: 255,
+            d: 60_000,
+        }];
+        let df = crate::to_data_frame(&rows).unwrap();
+        assert_eq!(
+            df.dtypes(),
+            [
+                DataType::Int8,
+                DataType::Int16,
+                DataType::UInt8,
+                DataType::UInt16,
+            ],
+        );
+
+        let s1 = Column::new("half".into(), [0.5_f32, -2.0])
+            .cast(&DataType::Float16)
+            .unwrap();
+        let df = df.hstack(&[s1.head(Some(1))]).unwrap();
+        let columns =
+            HashMap::<String, Vec<crate::Value>>::deserialize(super::Deserializer::columns(&df))
+                .unwrap();
+        assert_eq!(columns["a"], [crate::Value::Int8(-1)]);
+        assert_eq!(columns["b"], [crate::Value::Int16(-300)]);
+        assert_eq!(columns["c"], [crate::Value::UInt8(255)]);
+        assert_eq!(columns["d"], [crate::Value::UInt16(60_000)]);
+        assert_eq!(columns["half"], [crate::Value::Float32(0.5)]);
+    }
+
+    #[test]
+    fn test_columns_into_map() {
         let s1 = Column::new("Ocean".into(), ["Atlantic", "Indian"]);
         let s2 = Column::new("Area (km²)".into(), [106_460_000, 70_560_000]);
         let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
 
-        let rows = Vec::<Row<'_>>::deserialize(super::BorrowedDeserializer::rows(&df)).unwrap();
+        let columns = HashMap::<String, Vec<serde_json::Value>>::deserialize(
+            super::Deserializer::columns(&df),
+        )
+        .unwrap();
         assert_eq!(
-            rows,
+            columns,
+            HashMap::from([
+                ("Ocean".to_owned(), vec!["Atlantic".into(), "Indian".into()]),
+                (
+                    "Area (km²)".to_owned(),
+                    vec![106_460_000.into(), 70_560_000.into()],
+                ),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_columns_into_index_map() {
+        let s1 = Column::new("z".into(), [Some(1), None]);
+        let s2 = Column::new("a".into(), [Some("x"), None]);
+        let s3 = Column::new("m".into(), [Some(true), Some(false)]);
+        let df = DataFrame::new_infer_height(vec![s1, s2, s3]).unwrap();
+
+        let columns = IndexMap::<&str, Vec<serde_json::Value>>::deserialize(
+            super::BorrowedDeserializer::columns(&df),
+        )
+        .unwrap();
+        assert_eq!(
+            columns.into_iter().collect::<Vec<_>>(),
             [
-                Row {
-                    ocean: "Atlantic",
-                    area: 106_460_000,
-                },
-                Row {
-                    ocean: "Indian",
-                    area: 70_560_000,
-                },
+                ("z", vec![1.into(), serde_json::Value::Null]),
+                ("a", vec!["x".into(), serde_json::Value::Null]),
+                ("m", vec![true.into(), false.into()]),
             ],
         );
     }
+
+    #[test]
+    fn test_columns_with_key_normalizer() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Columns<'a> {
+            #[serde(borrow)]
+            ocean: Vec<&'a str>,
+            #[serde(rename = "area_km²")]
+            area: Vec<u64>,
+        }
+
+        let s1 = Column::new("Ocean".into(), ["Atlantic", "Indian"]);
+        let s2 = Column::new("Area (km²)".into(), [106_460_000, 70_560_000]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        let columns = Columns::deserialize(
+            super::BorrowedDeserializer::columns(&df)
+                .with_key_normalizer(super::super::KeyNormalizer::SnakeCase),
+        )
+        .unwrap();
+        assert_eq!(
+            columns,
+            Columns {
+                ocean: vec!["Atlantic", "Indian"],
+                area: vec![106_460_000, 70_560_000],
+            },
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_columns() {
+        use std::collections::BTreeMap;
+
+        let s1 = Column::new("a".into(), [1_i64, 2, 3]);
+        let s2 = Column::new("b".into(), [4_i64, 5, 6]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        let columns = super::Deserializer::columns(&df)
+            .par_columns::<HashMap<_, Vec<i64>>>()
+            .unwrap();
+        assert_eq!(
+            columns,
+            HashMap::from([
+                ("a".to_owned(), vec![1, 2, 3]),
+                ("b".to_owned(), vec![4, 5, 6]),
+            ]),
+        );
+
+        fn uppercase(key: &str) -> std::borrow::Cow<'_, str> {
+            key.to_uppercase().into()
+        }
+
+        assert_eq!(
+            super::Deserializer::columns(&df)
+                .with_key_normalizer(super::super::KeyNormalizer::Custom(&uppercase))
+                .par_columns::<BTreeMap<String, Vec<i64>>>()
+                .unwrap(),
+            BTreeMap::from([
+                ("A".to_owned(), vec![1, 2, 3]),
+                ("B".to_owned(), vec![4, 5, 6]),
+            ]),
+        );
+
+        // a different element type per column, narrowed like in `columns` mode
+        assert_eq!(
+            super::Deserializer::columns(&df)
+                .par_columns::<(Vec<u8>, Vec<Option<f64>>)>()
+                .unwrap(),
+            (vec![1, 2, 3], vec![Some(4.0), Some(5.0), Some(6.0)]),
+        );
+        assert!(
+            super::Deserializer::columns(&df)
+                .par_columns::<(Vec<u8>,)>()
+                .is_err()
+        );
+        let s3 = Column::new("c".into(), [1_i64, 2, 300]);
+        let df3 = DataFrame::new_infer_height(vec![s3]).unwrap();
+        assert!(
+            super::Deserializer::columns(&df3)
+                .par_columns::<(Vec<u8>,)>()
+                .is_err()
+        );
+
+        #[cfg(feature = "rows")]
+        assert!(
+            super::Deserializer::rows(&df)
+                .par_columns::<HashMap<String, Vec<i64>>>()
+                .is_err()
+        );
+    }
+
+    #[cfg(all(
+        feature = "parallel",
+        feature = "dtype-categorical",
+        feature = "dtype-i128",
+        feature = "dtype-u128",
+    ))]
+    #[test]
+    fn test_par_columns_types() {
+        use polars_core::datatypes::Categories;
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Columns {
+            a: Vec<char>,
+            b: Vec<Vec<u8>>,
+            c: Vec<i128>,
+            d: Vec<u128>,
+            e: Vec<String>,
+        }
+
+        let s1 = Column::new("a".into(), ["x", "é"]);
+        let s2 = Column::new("b".into(), [b"\x00\xff".as_slice(), b""]);
+        let s3 = Column::new("c".into(), [i128::MIN, 1]);
+        let s4 = Column::new("d".into(), [u128::MAX, 2]);
+        let s5 = Column::new("e".into(), ["red", "green"])
+            .cast(&DataType::from_categories(Categories::global()))
+            .unwrap();
+        let df = DataFrame::new_infer_height(vec![s1, s2, s3, s4, s5]).unwrap();
+
+        let columns = Columns::deserialize(super::Deserializer::columns(&df)).unwrap();
+        let (a, b, c, d, e) = super::Deserializer::columns(&df).par_columns().unwrap();
+        assert_eq!(Columns { a, b, c, d, e }, columns);
+        assert_eq!(columns.a, ['x', 'é']);
+        assert_eq!(columns.e, ["red", "green"]);
+
+        // categories are presented like in `columns` mode
+        let codes = super::Deserializer::columns(&df.select(["e"]).unwrap())
+            .with_categorical(super::super::CategoricalRepr::Code)
+            .par_columns::<HashMap<String, Vec<u32>>>()
+            .unwrap();
+        assert_eq!(
+            codes,
+            HashMap::<String, Vec<u32>>::deserialize(
+                super::Deserializer::columns(&df.select(["e"]).unwrap())
+                    .with_categorical(super::super::CategoricalRepr::Code),
+            )
+            .unwrap(),
+        );
+    }
+
+    #[cfg(feature = "rows")]
+    #[test]
+    fn test_rows() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Row<'a> {
+            #[serde(rename = "Ocean")]
+            ocean: &'a str,
+            #[serde(rename = "Area (km²)")]
+            area: u64,
+        }
+
+        let s1 = Column::new("Ocean".into(), ["Atlantic", "Indian"]);
+        let s2 = Column::new("Area (km²)".into(), [106_460_000, 70_560_000]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        let rows = Vec::<Row<'_>>::deserialize(super::BorrowedDeserializer::rows(&df)).unwrap();
+        assert_eq!(
+            rows,
+            [
+                Row {
+                    ocean: "Atlantic",
+                    area: 106_460_000,
+                },
+                Row {
+                    ocean: "Indian",
+                    area: 70_560_000,
+                },
+            ],
+        );
+    }
+
+    #[cfg(feature = "rows")]
+    #[test]
+    fn test_rows_with_separator() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Address<'a> {
+            city: &'a str,
+            zip: u32,
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Row<'a> {
+            name: &'a str,
+            #[serde(borrow)]
+            address: Option<Address<'a>>,
+        }
+
+        let s1 = Column::new("name".into(), ["Alice", "Bob"]);
+        let s2 = Column::new("address.city".into(), [Some("Kyoto"), None]);
+        let s3 = Column::new("address.zip".into(), [Some(6_008_501_u32), None]);
+        let df = DataFrame::new_infer_height(vec![s1, s2, s3]).unwrap();
+
+        let rows =
+            Vec::<Row<'_>>::deserialize(super::BorrowedDeserializer::rows(&df).with_separator("."))
+                .unwrap();
+        assert_eq!(
+            rows,
+            [
+                Row {
+                    name: "Alice",
+                    address: Some(Address {
+                        city: "Kyoto",
+                        zip: 6_008_501,
+                    }),
+                },
+                Row {
+                    name: "Bob",
+                    address: None,
+                },
+            ],
+        );
+    }
+
+    #[cfg(feature = "rows")]
+    #[test]
+    fn test_rows_with_key_normalizer() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Row<'a> {
+            ocean: &'a str,
+            area: u64,
+        }
+
+        let s1 = Column::new("OCEAN".into(), ["Atlantic", "Indian"]);
+        let s2 = Column::new("Area".into(), [106_460_000, 70_560_000]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        let rows = Vec::<Row<'_>>::deserialize(
+            super::BorrowedDeserializer::rows(&df)
+                .with_key_normalizer(super::super::KeyNormalizer::Lowercase),
+        )
+        .unwrap();
+        assert_eq!(
+            rows,
+            [
+                Row {
+                    ocean: "Atlantic",
+                    area: 106_460_000,
+                },
+                Row {
+                    ocean: "Indian",
+                    area: 70_560_000,
+                },
+            ],
+        );
+    }
+
+    // fields are normalized too, so `areaKm` and `OCEAN` match their columns case-insensitively
+    #[test]
+    fn test_key_normalizer_fields() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Columns {
+            #[serde(rename = "OCEAN")]
+            ocean: Vec<String>,
+            area_km: Vec<u64>,
+        }
+
+        let s1 = Column::new("Ocean".into(), ["Atlantic", "Indian"]);
+        let s2 = Column::new("AreaKm".into(), [106_460_000_u64, 70_560_000]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        assert_eq!(
+            Columns::deserialize(
+                super::Deserializer::columns(&df)
+                    .with_key_normalizer(super::super::KeyNormalizer::Lowercase),
+            )
+            .unwrap(),
+            Columns {
+                ocean: vec!["Atlantic".to_owned(), "Indian".to_owned()],
+                area_km: vec![106_460_000, 70_560_000],
+            },
+        );
+
+        // other targets see the normalized column names
+        let columns = HashMap::<String, Vec<crate::Value>>::deserialize(
+            super::Deserializer::columns(&df)
+                .with_key_normalizer(super::super::KeyNormalizer::Lowercase),
+        )
+        .unwrap();
+        assert!(columns.contains_key("areakm"), "{columns:?}");
+
+        #[cfg(feature = "rows")]
+        {
+            #[derive(Debug, PartialEq, Deserialize)]
+            #[serde(rename_all = "camelCase")]
+            struct Row<'a> {
+                #[serde(rename = "OCEAN")]
+                ocean: &'a str,
+                area_km: u64,
+            }
+
+            let rows = Vec::<Row<'_>>::deserialize(
+                super::BorrowedDeserializer::rows(&df)
+                    .with_key_normalizer(super::super::KeyNormalizer::Lowercase),
+            )
+            .unwrap();
+            assert_eq!(
+                rows[1],
+                Row {
+                    ocean: "Indian",
+                    area_km: 70_560_000,
+                },
+            );
+            assert_eq!(
+                Row::deserialize(
+                    super::BorrowedDeserializer::row(&df, 0)
+                        .with_key_normalizer(super::super::KeyNormalizer::Lowercase),
+                )
+                .unwrap(),
+                Row {
+                    ocean: "Atlantic",
+                    area_km: 106_460_000,
+                },
+            );
+        }
+    }
+
+    #[cfg(all(feature = "parallel", feature = "rows"))]
+    #[test]
+    fn test_par_rows() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Row {
+            a: u32,
+            b: String,
+        }
+
+        let s1 = Column::new("a".into(), (0..1000).collect::<Vec<u32>>());
+        let s2 = Column::new(
+            "b".into(),
+            (0..1000).map(|i| i.to_string()).collect::<Vec<_>>(),
+        );
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        let rows = super::Deserializer::rows(&df).par_rows::<Row>().unwrap();
+        assert_eq!(
+            rows,
+            Vec::<Row>::deserialize(super::Deserializer::rows(&df)).unwrap(),
+        );
+        assert_eq!(rows.len(), 1000);
+        assert!(super::Deserializer::columns(&df).par_rows::<Row>().is_err());
+    }
+
+    #[cfg(feature = "rows")]
+    #[test]
+    fn test_rows_chunked() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Row {
+            a: u32,
+            b: Option<bool>,
+        }
+
+        let mut df = DataFrame::new_infer_height(vec![
+            Column::new("a".into(), [0_u32, 1]),
+            Column::new("b".into(), [Some(true), None]),
+        ])
+        .unwrap();
+        df.vstack_mut(
+            &DataFrame::new_infer_height(vec![
+                Column::new("a".into(), [2_u32]),
+                Column::new("b".into(), [Some(false)]),
+            ])
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(df.first_col_n_chunks(), 2);
+
+        let rows = Vec::<Row>::deserialize(super::Deserializer::rows(&df)).unwrap();
+        assert_eq!(
+            rows,
+            [
+                Row {
+                    a: 0,
+                    b: Some(true)
+                },
+                Row { a: 1, b: None },
+                Row {
+                    a: 2,
+                    b: Some(false),
+                },
+            ],
+        );
+    }
+
+    #[cfg(feature = "rows")]
+    #[test]
+    fn test_from_rows() {
+        #[derive(Debug, PartialEq, Deserialize, serde::Serialize)]
+        struct Row<'a> {
+            name: &'a str,
+            score: Option<f64>,
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Columns<'a> {
+            #[serde(borrow)]
+            name: Vec<&'a str>,
+            score: Vec<Option<f64>>,
+        }
+
+        let rows = [
+            Row {
+                name: "Alice",
+                score: Some(1.5),
+            },
+            Row {
+                name: "Bob",
+                score: None,
+            },
+        ];
+        let df = crate::to_data_frame(&rows).unwrap();
+        assert_eq!(crate::from_rows::<Row<'_>>(&df).unwrap(), rows);
+        assert_eq!(
+            crate::from_columns::<Columns<'_>>(&df).unwrap(),
+            Columns {
+                name: vec!["Alice", "Bob"],
+                score: vec![Some(1.5), None],
+            },
+        );
+    }
+
+    #[cfg(feature = "rows")]
+    #[test]
+    fn test_row() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Row<'a> {
+            #[serde(rename = "Ocean")]
+            ocean: &'a str,
+            #[serde(rename = "Area (km²)")]
+            area: u64,
+        }
+
+        let s1 = Column::new("Ocean".into(), ["Atlantic", "Indian"]);
+        let s2 = Column::new("Area (km²)".into(), [106_460_000, 70_560_000]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        assert_eq!(
+            Row::deserialize(super::BorrowedDeserializer::row(&df, 1)).unwrap(),
+            Row {
+                ocean: "Indian",
+                area: 70_560_000,
+            },
+        );
+        assert!(Row::deserialize(super::BorrowedDeserializer::row(&df, 2)).is_err());
+        assert!(Row::deserialize(super::BorrowedDeserializer::single_row(&df)).is_err());
+        assert_eq!(
+            Row::deserialize(super::BorrowedDeserializer::single_row(&df.head(Some(1)))).unwrap(),
+            Row {
+                ocean: "Atlantic",
+                area: 106_460_000,
+            },
+        );
+    }
+
+    #[cfg(feature = "rows")]
+    #[test]
+    fn test_rows_binary_and_string() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Row<'a> {
+            name: &'a str,
+            payload: &'a [u8],
+        }
+
+        let s1 = Column::new("name".into(), [b"Atlantic".as_slice()])
+            .cast(&DataType::BinaryOffset)
+            .unwrap();
+        let s2 = Column::new("payload".into(), ["abc"]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        assert_eq!(
+            Vec::<Row>::deserialize(super::BorrowedDeserializer::rows(&df)).unwrap(),
+            [Row {
+                name: "Atlantic",
+                payload: b"abc",
+            }],
+        );
+    }
+
+    #[cfg(feature = "rows")]
+    #[test]
+    fn test_rows_char() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Row {
+            letter: Option<char>,
+            code: char,
+        }
+
+        let s1 = Column::new("letter".into(), [Some("x"), None]);
+        let s2 = Column::new("code".into(), [0x41_u32, 0x3042]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        assert_eq!(
+            Vec::<Row>::deserialize(super::Deserializer::rows(&df)).unwrap(),
+            [
+                Row {
+                    letter: Some('x'),
+                    code: 'A',
+                },
+                Row {
+                    letter: None,
+                    code: 'あ',
+                },
+            ],
+        );
+
+        let s1 = Column::new("letter".into(), [""]);
+        let df = DataFrame::new_infer_height(vec![s1]).unwrap();
+        let e = Vec::<HashMap<&str, char>>::deserialize(super::BorrowedDeserializer::rows(&df))
+            .unwrap_err();
+        assert!(e.to_string().contains("a single code point"), "{e}");
+    }
+
+    #[cfg(feature = "rows")]
+    #[test]
+    fn test_rows_newtype() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Rows<'a>(#[serde(borrow)] Vec<Ocean<'a>>);
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Ocean<'a>(#[serde(borrow)] Row<'a>);
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Row<'a> {
+            name: &'a str,
+            depth: Meters,
+        }
+
+        let s1 = Column::new("name".into(), ["Atlantic"]);
+        let s2 = Column::new("depth".into(), [8_486.0]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        assert_eq!(
+            Rows::deserialize(super::BorrowedDeserializer::rows(&df)).unwrap(),
+            Rows(vec![Ocean(Row {
+                name: "Atlantic",
+                depth: Meters(8_486.0),
+            })]),
+        );
+    }
+
+    #[cfg(feature = "rows")]
+    #[test]
+    fn test_keyed() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Price {
+            bid: f64,
+            ask: f64,
+        }
+
+        let s1 = Column::new("symbol".into(), ["AAA", "BBB", "AAA"]);
+        let s2 = Column::new("bid".into(), [1.0, 2.0, 1.5]);
+        let s3 = Column::new("ask".into(), [1.1, 2.1, 1.6]);
+        let df = DataFrame::new_infer_height(vec![s1, s2, s3]).unwrap();
+
+        assert!(
+            HashMap::<&str, Price>::deserialize(super::BorrowedDeserializer::keyed(
+                &df,
+                &["symbol"],
+            ))
+            .is_err()
+        );
+
+        let prices = IndexMap::<&str, Price>::deserialize(
+            super::BorrowedDeserializer::keyed(&df, &["symbol"])
+                .with_duplicate_keys(crate::de::DuplicateKeys::LastWins),
+        )
+        .unwrap();
+        assert_eq!(
+            prices.into_iter().collect::<Vec<_>>(),
+            [
+                ("BBB", Price { bid: 2.0, ask: 2.1 }),
+                ("AAA", Price { bid: 1.5, ask: 1.6 }),
+            ],
+        );
+
+        let prices = HashMap::<(String, u32), HashMap<String, f64>>::deserialize(
+            super::Deserializer::keyed(
+                &df.with_row_index("i".into(), None).unwrap(),
+                &["symbol", "i"],
+            ),
+        )
+        .unwrap();
+        assert_eq!(
+            prices[&("AAA".to_owned(), 2)],
+            HashMap::from([("bid".to_owned(), 1.5), ("ask".to_owned(), 1.6)]),
+        );
+    }
+
+    #[cfg(feature = "group-by")]
+    #[test]
+    fn test_grouped() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Group<'a> {
+            symbol: &'a str,
+            #[serde(borrow)]
+            trades: Vec<Trade<'a>>,
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Trade<'a> {
+            side: &'a str,
+            price: f64,
+        }
+
+        let s1 = Column::new("symbol".into(), ["BBB", "AAA", "BBB"]);
+        let s2 = Column::new("side".into(), ["buy", "sell", "sell"]);
+        let s3 = Column::new("price".into(), [2.0, 1.0, 2.5]);
+        let df = DataFrame::new_infer_height(vec![s1, s2, s3]).unwrap();
+
+        assert_eq!(
+            Vec::<Group>::deserialize(super::BorrowedDeserializer::grouped(
+                &df,
+                &["symbol"],
+                "trades",
+            ))
+            .unwrap(),
+            [
+                Group {
+                    symbol: "BBB",
+                    trades: vec![
+                        Trade {
+                            side: "buy",
+                            price: 2.0,
+                        },
+                        Trade {
+                            side: "sell",
+                            price: 2.5,
+                        },
+                    ],
+                },
+                Group {
+                    symbol: "AAA",
+                    trades: vec![Trade {
+                        side: "sell",
+                        price: 1.0,
+                    }],
+                },
+            ],
+        );
+
+        let groups = Vec::<HashMap<String, serde_json::Value>>::deserialize(
+            super::Deserializer::grouped(&df, &["symbol", "side"], "prices"),
+        )
+        .unwrap();
+        assert_eq!(groups.len(), 3);
+        assert_eq!(
+            groups[2],
+            HashMap::from([
+                ("symbol".to_owned(), "BBB".into()),
+                ("side".to_owned(), "sell".into()),
+                ("prices".to_owned(), serde_json::json!([{ "price": 2.5 }])),
+            ]),
+        );
+
+        let mut chunked = df.clone();
+        chunked.vstack_mut(&df).unwrap();
+        assert_eq!(chunked.first_col_n_chunks(), 2);
+        let groups = Vec::<Group>::deserialize(super::BorrowedDeserializer::grouped(
+            &chunked,
+            &["symbol"],
+            "trades",
+        ))
+        .unwrap();
+        assert_eq!(
+            groups
+                .iter()
+                .map(|group| (group.symbol, group.trades.len()))
+                .collect::<Vec<_>>(),
+            [("BBB", 4), ("AAA", 2)],
+        );
+        assert_eq!(
+            groups[1].trades[1],
+            Trade {
+                side: "sell",
+                price: 1.0,
+            },
+        );
+    }
+
+    #[cfg(feature = "dtype-categorical")]
+    #[test]
+    fn test_categorical() {
+        use polars_core::datatypes::{CategoricalPhysical, Categories};
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Category {
+            code: u32,
+            label: String,
+        }
+
+        let dtype =
+            DataType::from_categories(Categories::random("".into(), CategoricalPhysical::U32));
+        let column = |values: &[&str]| Column::new("color".into(), values).cast(&dtype).unwrap();
+        let mut df = DataFrame::new_infer_height(vec![column(&["red", "green"])]).unwrap();
+        df.vstack_mut(&DataFrame::new_infer_height(vec![column(&["red"])]).unwrap())
+            .unwrap();
+        assert_eq!(df.first_col_n_chunks(), 2);
+
+        assert_eq!(
+            HashMap::<String, Vec<String>>::deserialize(super::Deserializer::columns(&df)).unwrap(),
+            HashMap::from([(
+                "color".to_owned(),
+                vec!["red".to_owned(), "green".to_owned(), "red".to_owned()]
+            )]),
+        );
+        assert_eq!(
+            HashMap::<String, Vec<u32>>::deserialize(
+                super::BorrowedDeserializer::columns(&df)
+                    .with_categorical(super::super::CategoricalRepr::Code),
+            )
+            .unwrap(),
+            HashMap::from([("color".to_owned(), vec![0, 1, 0])]),
+        );
+        assert_eq!(
+            super::categories(df.column("color").unwrap()).unwrap(),
+            [Some("red"), Some("green")],
+        );
+        assert!(super::categories(&Column::new("color".into(), ["red"])).is_err());
+
+        let both = HashMap::<String, Vec<Category>>::deserialize(
+            super::Deserializer::columns(&df).with_categorical(super::super::CategoricalRepr::Both),
+        )
+        .unwrap();
+        assert_eq!(
+            both["color"][1],
+            Category {
+                code: 1,
+                label: "green".to_owned(),
+            },
+        );
+
+        #[cfg(feature = "rows")]
+        {
+            #[derive(Debug, PartialEq, Deserialize)]
+            struct Row {
+                color: Category,
+            }
+
+            assert_eq!(
+                Vec::<HashMap<String, u32>>::deserialize(
+                    super::Deserializer::rows(&df)
+                        .with_categorical(super::super::CategoricalRepr::Code),
+                )
+                .unwrap(),
+                [0, 1, 0].map(|code| HashMap::from([("color".to_owned(), code)])),
+            );
+            assert_eq!(
+                Vec::<Row>::deserialize(
+                    super::BorrowedDeserializer::rows(&df)
+                        .with_categorical(super::super::CategoricalRepr::Both),
+                )
+                .unwrap()[2],
+                Row {
+                    color: Category {
+                        code: 0,
+                        label: "red".to_owned(),
+                    },
+                },
+            );
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Meters(f64);
+
+    impl<'de> Deserialize<'de> for Meters {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            struct Visitor;
+
+            impl<'de> serde::de::Visitor<'de> for Visitor {
+                type Value = Meters;
+
+                fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    formatter.write_str("a newtype struct")
+                }
+
+                fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    f64::deserialize(deserializer).map(Meters)
+                }
+            }
+
+            deserializer.deserialize_newtype_struct("Meters", Visitor)
+        }
+    }
 }
@@ -0,0 +1,41 @@
+use serde::de;
+
+/// Returns the field names of the struct `T` as seen by serde, i.e. after `rename`.
+///
+/// Use it to read only the columns `T` needs, e.g. `LazyFrame::select` before collecting
+/// so that the projection is pushed down into the scan.
+pub fn field_names<'de, T>() -> Result<&'static [&'static str], super::Error>
+where
+    T: de::Deserialize<'de>,
+{
+    match crate::trace::shape::<T>() {
+        Some(crate::trace::Shape::Struct(fields)) => Ok(fields),
+        _ => Err(de::Error::custom(format_args!(
+            "{} is not a struct",
+            std::any::type_name::<T>(),
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    #[test]
+    fn test_field_names() {
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        struct Row<'a> {
+            #[serde(rename = "Ocean")]
+            ocean: &'a str,
+            area: Option<u64>,
+        }
+
+        assert_eq!(super::field_names::<Row<'_>>().unwrap(), ["Ocean", "area"],);
+        assert_eq!(
+            super::field_names::<Option<Row<'_>>>().unwrap(),
+            ["Ocean", "area"],
+        );
+        assert!(super::field_names::<Vec<u64>>().is_err());
+    }
+}
@@ -0,0 +1,320 @@
+use polars_core::datatypes::AnyValue;
+use polars_core::error::PolarsResult;
+use polars_core::frame::DataFrame;
+use polars_core::frame::group_by::GroupsIndicator;
+use polars_core::frame::row::Row;
+use serde::de;
+use std::borrow::Cow;
+use std::vec;
+
+pub struct Group<'a>(Vec<AnyValue<'a>>, Vec<Row<'a>>);
+
+pub fn split<'a>(
+    value: &'a DataFrame,
+    keys: &[&str],
+) -> Result<(Vec<&'a str>, Vec<Group<'a>>), super::Error> {
+    let group_by = value.group_by_stable(keys.iter().copied())?;
+    let indices = keys
+        .iter()
+        .map(|key| value.try_get_column_index(key))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let names = value
+        .columns()
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !indices.contains(i))
+        .map(|(_, column)| column.name().as_str())
+        .collect::<Vec<_>>();
+
+    // the frame is read chunk-wise once, then each row is moved into its group
+    let mut rows = super::data_frame::Rows::new(value)
+        .map(|row| {
+            let mut key = vec![AnyValue::Null; indices.len()];
+            let mut values = Vec::with_capacity(names.len());
+            for (i, v) in row.0.into_iter().enumerate() {
+                if let Some(j) = indices.iter().position(|index| *index == i) {
+                    key[j] = v;
+                } else {
+                    values.push(v);
+                }
+            }
+            Some((key, Row(values)))
+        })
+        .collect::<Vec<_>>();
+
+    let groups = group_by
+        .get_groups()
+        .iter()
+        .map(|group| {
+            let indices = match group {
+                GroupsIndicator::Idx((_, all)) => all.iter().map(|i| *i as usize).collect(),
+                GroupsIndicator::Slice([first, len]) => {
+                    (first as usize..(first + len) as usize).collect::<Vec<_>>()
+                }
+            };
+            let mut keys = None;
+            let rows = indices
+                .into_iter()
+                .map(|i| {
+                    let (key, row) = rows[i].take().ok_or_else(|| {
+                        <super::Error as de::Error>::custom("row is in more than one group")
+                    })?;
+                    keys.get_or_insert(key);
+                    Ok(row)
+                })
+                .collect::<Result<Vec<_>, super::Error>>()?;
+            Ok(Group(keys.unwrap_or_default(), rows))
+        })
+        .collect::<Result<Vec<_>, super::Error>>()?;
+    Ok((names, groups))
+}
+
+#[derive(Clone, Copy)]
+pub struct Layout<'t, 'a> {
+    pub keys: &'t [&'t str],
+    pub items: &'t str,
+    pub names: &'t [&'a str],
+    pub tree: Option<&'t super::nested::Tree<'a>>,
+    pub key_normalizer: Option<super::KeyNormalizer<'a>>,
+    pub categorical: super::CategoricalRepr,
+}
+
+impl Layout<'_, '_> {
+    fn key<'b>(&self, name: &'b str, fields: &'static [&'static str]) -> Cow<'b, str> {
+        super::key::normalize(self.key_normalizer, name, fields)
+    }
+}
+
+pub struct Deserializer<'t, 'a>(Layout<'t, 'a>, Group<'a>);
+
+impl<'t, 'a> Deserializer<'t, 'a> {
+    pub fn new(layout: Layout<'t, 'a>, group: Group<'a>) -> Self {
+        Self(layout, group)
+    }
+}
+
+impl<'de, 't, 'a> de::Deserializer<'de> for Deserializer<'t, 'a> {
+    type Error = super::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_struct("", &[], visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_map(GroupAccess {
+            layout: self.0,
+            fields,
+            keys: self.1.0.into_iter(),
+            rows: Some(self.1.1),
+            index: 0,
+        })
+    }
+
+    deserialize_delegate!(@ except deserialize_struct);
+}
+
+impl<'de, 't, 'a> de::IntoDeserializer<'de, super::Error> for Deserializer<'t, 'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+struct GroupAccess<'t, 'a> {
+    layout: Layout<'t, 'a>,
+    fields: &'static [&'static str],
+    keys: vec::IntoIter<AnyValue<'a>>,
+    rows: Option<Vec<Row<'a>>>,
+    index: usize,
+}
+
+impl<'de, 't, 'a> de::MapAccess<'de> for GroupAccess<'t, 'a> {
+    type Error = super::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        let key = match self.layout.keys.get(self.index) {
+            Some(key) => *key,
+            None if self.rows.is_some() => self.layout.items,
+            None => return Ok(None),
+        };
+        self.index += 1;
+        seed.deserialize(super::key::Deserializer::new(
+            self.layout.key(key, self.fields),
+        ))
+        .map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        if let Some(value) = self.keys.next() {
+            return seed.deserialize(
+                super::AnyValueDeserializer::new(value).with_categorical(self.layout.categorical),
+            );
+        }
+        let Some(rows) = self.rows.take() else {
+            return Err(de::Error::custom("value is missing"));
+        };
+        let layout = self.layout;
+        if let Some(tree) = layout.tree {
+            seed.deserialize(de::value::SeqDeserializer::new(rows.into_iter().map(
+                |row| {
+                    super::nested::Deserializer::new(tree, row).with_categorical(layout.categorical)
+                },
+            )))
+        } else {
+            seed.deserialize(de::value::SeqDeserializer::new(rows.into_iter().map(
+                |row| {
+                    let deserializer =
+                        super::RowDeserializer::new(layout.names.iter().copied(), row)
+                            .with_categorical(layout.categorical);
+                    match layout.key_normalizer {
+                        Some(key_normalizer) => deserializer.with_key_normalizer(key_normalizer),
+                        None => deserializer,
+                    }
+                },
+            )))
+        }
+    }
+}
+
+pub struct BorrowedDeserializer<'t, 'de>(Layout<'t, 'de>, Group<'de>);
+
+impl<'t, 'de> BorrowedDeserializer<'t, 'de> {
+    pub fn new(layout: Layout<'t, 'de>, group: Group<'de>) -> Self {
+        Self(layout, group)
+    }
+}
+
+impl<'t, 'de> de::Deserializer<'de> for BorrowedDeserializer<'t, 'de> {
+    type Error = super::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_struct("", &[], visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_map(BorrowedGroupAccess {
+            layout: self.0,
+            fields,
+            keys: self.1.0.into_iter(),
+            rows: Some(self.1.1),
+            index: 0,
+        })
+    }
+
+    deserialize_delegate!(@ except deserialize_struct);
+}
+
+impl<'t, 'de> de::IntoDeserializer<'de, super::Error> for BorrowedDeserializer<'t, 'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+struct BorrowedGroupAccess<'t, 'de> {
+    layout: Layout<'t, 'de>,
+    fields: &'static [&'static str],
+    keys: vec::IntoIter<AnyValue<'de>>,
+    rows: Option<Vec<Row<'de>>>,
+    index: usize,
+}
+
+impl<'t, 'de> de::MapAccess<'de> for BorrowedGroupAccess<'t, 'de> {
+    type Error = super::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        let key = match self.layout.keys.get(self.index) {
+            Some(key) => *key,
+            None if self.rows.is_some() => self.layout.items,
+            None => return Ok(None),
+        };
+        self.index += 1;
+        seed.deserialize(super::key::Deserializer::new(
+            self.layout.key(key, self.fields),
+        ))
+        .map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        if let Some(value) = self.keys.next() {
+            return seed.deserialize(
+                super::BorrowedAnyValueDeserializer::new(value)
+                    .with_categorical(self.layout.categorical),
+            );
+        }
+        let Some(rows) = self.rows.take() else {
+            return Err(de::Error::custom("value is missing"));
+        };
+        let layout = self.layout;
+        if let Some(tree) = layout.tree {
+            seed.deserialize(de::value::SeqDeserializer::new(rows.into_iter().map(
+                |row| {
+                    super::nested::BorrowedDeserializer::new(tree, row)
+                        .with_categorical(layout.categorical)
+                },
+            )))
+        } else {
+            seed.deserialize(de::value::SeqDeserializer::new(rows.into_iter().map(
+                |row| {
+                    let deserializer =
+                        super::BorrowedRowDeserializer::new(layout.names.iter().copied(), row)
+                            .with_categorical(layout.categorical);
+                    match layout.key_normalizer {
+                        Some(key_normalizer) => deserializer.with_key_normalizer(key_normalizer),
+                        None => deserializer,
+                    }
+                },
+            )))
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use serde::de;
+use std::borrow::Cow;
+
+/// Rewrites column names before they are matched against field names.
+///
+/// Struct fields are normalized the same way, so a column matches a field whenever both have the
+/// same normalized name, e.g. `"AreaKm"` matches `areaKm` with [`Lowercase`](Self::Lowercase).
+/// Other targets, such as maps, see the normalized column names.
+#[derive(Clone, Copy)]
+pub enum KeyNormalizer<'f> {
+    /// `"Ocean"` becomes `"ocean"`.
+    Lowercase,
+    /// `"oceanName"` becomes `"ocean_name"`.
+    SnakeCase,
+    /// Any function of the column name.
+    Custom(&'f (dyn Fn(&str) -> Cow<'_, str> + Sync)),
+}
+
+impl KeyNormalizer<'_> {
+    pub fn apply<'a>(&self, key: &'a str) -> Cow<'a, str> {
+        match self {
+            Self::Lowercase => {
+                if key.chars().any(char::is_uppercase) {
+                    Cow::Owned(key.to_lowercase())
+                } else {
+                    Cow::Borrowed(key)
+                }
+            }
+            Self::SnakeCase => {
+                let v = snake_case(key);
+                if v == key {
+                    Cow::Borrowed(key)
+                } else {
+                    Cow::Owned(v)
+                }
+            }
+            Self::Custom(f) => f(key),
+        }
+    }
+
+    /// Finds the field among `fields` whose normalized name is `key`, an already normalized name.
+    pub(crate) fn field(&self, key: &str, fields: &'static [&'static str]) -> Option<&'static str> {
+        fields
+            .iter()
+            .find(|field| **field == key)
+            .or_else(|| fields.iter().find(|field| self.apply(field) == key))
+            .copied()
+    }
+}
+
+/// Normalizes a column name, then resolves it to one of `fields` if a field matches it.
+pub(crate) fn normalize<'a>(
+    key_normalizer: Option<KeyNormalizer<'_>>,
+    key: &'a str,
+    fields: &'static [&'static str],
+) -> Cow<'a, str> {
+    let Some(key_normalizer) = key_normalizer else {
+        return Cow::Borrowed(key);
+    };
+    let key = key_normalizer.apply(key);
+    match key_normalizer.field(&key, fields) {
+        Some(field) => Cow::Borrowed(field),
+        None => key,
+    }
+}
+
+fn snake_case(key: &str) -> String {
+    let chars = key.chars().collect::<Vec<_>>();
+    let mut v = String::with_capacity(key.len());
+    let mut separated = true;
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_alphanumeric() {
+            // split `oceanName` and `HTTPServer` at the start of each word
+            if c.is_uppercase()
+                && i > 0
+                && (chars[i - 1].is_lowercase()
+                    || (chars[i - 1].is_uppercase()
+                        && chars.get(i + 1).is_some_and(|c| c.is_lowercase())))
+            {
+                separated = false;
+            }
+            if !separated && !v.is_empty() {
+                v.push('_');
+            }
+            separated = true;
+            v.extend(c.to_lowercase());
+        } else {
+            separated = false;
+        }
+    }
+    v
+}
+
+pub struct Deserializer<'a>(Cow<'a, str>);
+
+impl<'a> Deserializer<'a> {
+    pub fn new(value: Cow<'a, str>) -> Self {
+        Self(value)
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for Deserializer<'a> {
+    type Error = super::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.0 {
+            Cow::Borrowed(v) => visitor.visit_str(v),
+            Cow::Owned(v) => visitor.visit_string(v),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    // keys may also be unit variants, e.g. `HashMap<Field, Vec<T>>`
+    fn deserialize_enum<V>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.0 {
+            Cow::Borrowed(v) => visitor.visit_enum(de::value::StrDeserializer::new(v)),
+            Cow::Owned(v) => visitor.visit_enum(de::value::StringDeserializer::new(v)),
+        }
+    }
+
+    deserialize_delegate! {
+        deserialize_bool
+        deserialize_i8
+        deserialize_i16
+        deserialize_i32
+        deserialize_i64
+        deserialize_u8
+        deserialize_u16
+        deserialize_u32
+        deserialize_u64
+        deserialize_f32
+        deserialize_f64
+        deserialize_char
+        deserialize_str
+        deserialize_string
+        deserialize_bytes
+        deserialize_byte_buf
+        deserialize_unit
+        deserialize_unit_struct
+        deserialize_newtype_struct
+        deserialize_seq
+        deserialize_tuple
+        deserialize_tuple_struct
+        deserialize_map
+        deserialize_struct
+        deserialize_identifier
+        deserialize_ignored_any
+    }
+}
+
+impl<'de, 'a> de::IntoDeserializer<'de, super::Error> for Deserializer<'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+pub struct BorrowedDeserializer<'de>(Cow<'de, str>);
+
+impl<'de> BorrowedDeserializer<'de> {
+    pub fn new(value: Cow<'de, str>) -> Self {
+        Self(value)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for BorrowedDeserializer<'de> {
+    type Error = super::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.0 {
+            Cow::Borrowed(v) => visitor.visit_borrowed_str(v),
+            Cow::Owned(v) => visitor.visit_string(v),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.0 {
+            Cow::Borrowed(v) => visitor.visit_enum(de::value::BorrowedStrDeserializer::new(v)),
+            Cow::Owned(v) => visitor.visit_enum(de::value::StringDeserializer::new(v)),
+        }
+    }
+
+    deserialize_delegate! {
+        deserialize_bool
+        deserialize_i8
+        deserialize_i16
+        deserialize_i32
+        deserialize_i64
+        deserialize_u8
+        deserialize_u16
+        deserialize_u32
+        deserialize_u64
+        deserialize_f32
+        deserialize_f64
+        deserialize_char
+        deserialize_str
+        deserialize_string
+        deserialize_bytes
+        deserialize_byte_buf
+        deserialize_unit
+        deserialize_unit_struct
+        deserialize_newtype_struct
+        deserialize_seq
+        deserialize_tuple
+        deserialize_tuple_struct
+        deserialize_map
+        deserialize_struct
+        deserialize_identifier
+        deserialize_ignored_any
+    }
+}
+
+impl<'de> de::IntoDeserializer<'de, super::Error> for BorrowedDeserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeyNormalizer;
+
+    #[test]
+    fn test_snake_case() {
+        for (key, expected) in [
+            ("Ocean", "ocean"),
+            ("Area (km²)", "area_km²"),
+            ("oceanName", "ocean_name"),
+            ("HTTPServer", "http_server"),
+            ("already_snake", "already_snake"),
+            ("  Max. Depth  ", "max_depth"),
+        ] {
+            assert_eq!(KeyNormalizer::SnakeCase.apply(key), expected);
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        const FIELDS: &[&str] = &["areaKm", "AREA", "ocean_name"];
+        for (key, expected) in [
+            ("AreaKm", "areaKm"),
+            ("area", "AREA"),
+            ("Ocean_Name", "ocean_name"),
+            ("Depth", "depth"),
+        ] {
+            assert_eq!(
+                super::normalize(Some(KeyNormalizer::Lowercase), key, FIELDS),
+                expected,
+            );
+        }
+        assert_eq!(
+            super::normalize(Some(KeyNormalizer::SnakeCase), "OceanName", FIELDS),
+            "ocean_name",
+        );
+        assert_eq!(super::normalize(None, "AreaKm", FIELDS), "AreaKm");
+    }
+}
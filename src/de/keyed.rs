@@ -0,0 +1,238 @@
+use polars_core::datatypes::AnyValue;
+use polars_core::error::PolarsResult;
+use polars_core::frame::DataFrame;
+use polars_core::frame::row::Row;
+use serde::de;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::vec;
+
+/// What to do when several rows have the same key.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicateKeys {
+    /// Fail with an error.
+    #[default]
+    Error,
+    /// Keep the last row with the key.
+    LastWins,
+}
+
+type Rows<'a> = Vec<(Vec<AnyValue<'a>>, Row<'a>)>;
+
+pub fn split<'a>(
+    value: &'a DataFrame,
+    keys: &[&str],
+    duplicate_keys: DuplicateKeys,
+) -> Result<(Vec<&'a str>, Rows<'a>), super::Error> {
+    let indices = keys
+        .iter()
+        .map(|key| value.try_get_column_index(key))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let names = value
+        .columns()
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !indices.contains(i))
+        .map(|(_, column)| column.name().as_str())
+        .collect::<Vec<_>>();
+
+    let mut rows = Vec::with_capacity(value.height());
+    // categorical mappings are never mutated while hashing the keys
+    #[allow(clippy::mutable_key_type)]
+    let mut positions = HashMap::with_capacity(value.height());
+    for row in super::data_frame::Rows::new(value) {
+        let mut key = vec![AnyValue::Null; indices.len()];
+        let mut values = Vec::with_capacity(names.len());
+        for (i, v) in row.0.into_iter().enumerate() {
+            if let Some(j) = indices.iter().position(|index| *index == i) {
+                key[j] = v;
+            } else {
+                values.push(v);
+            }
+        }
+        match positions.entry(key.clone()) {
+            Entry::Vacant(entry) => {
+                entry.insert(rows.len());
+            }
+            Entry::Occupied(mut entry) => match duplicate_keys {
+                DuplicateKeys::Error => {
+                    return Err(de::Error::custom(format_args!(
+                        "duplicate key {:?}",
+                        entry.key(),
+                    )));
+                }
+                DuplicateKeys::LastWins => {
+                    rows[*entry.get()] = None;
+                    entry.insert(rows.len());
+                }
+            },
+        }
+        rows.push(Some((key, Row(values))));
+    }
+    Ok((names, rows.into_iter().flatten().collect()))
+}
+
+pub struct Access<'t, 'a> {
+    names: &'t [&'a str],
+    iter: vec::IntoIter<(Vec<AnyValue<'a>>, Row<'a>)>,
+    value: Option<Row<'a>>,
+    tree: Option<&'t super::nested::Tree<'a>>,
+    key_normalizer: Option<super::KeyNormalizer<'t>>,
+    categorical: super::CategoricalRepr,
+}
+
+impl<'t, 'a> Access<'t, 'a> {
+    pub fn new(
+        names: &'t [&'a str],
+        rows: Rows<'a>,
+        tree: Option<&'t super::nested::Tree<'a>>,
+        key_normalizer: Option<super::KeyNormalizer<'t>>,
+        categorical: super::CategoricalRepr,
+    ) -> Self {
+        Self {
+            names,
+            iter: rows.into_iter(),
+            value: None,
+            tree,
+            key_normalizer,
+            categorical,
+        }
+    }
+}
+
+impl<'de, 't, 'a> de::MapAccess<'de> for Access<'t, 'a> {
+    type Error = super::Error;
+
+    // a single key column is the key itself, several are a tuple
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        if let Some((mut key, value)) = self.iter.next() {
+            self.value = Some(value);
+            if key.len() == 1 {
+                seed.deserialize(
+                    super::AnyValueDeserializer::new(key.remove(0))
+                        .with_categorical(self.categorical),
+                )
+            } else {
+                seed.deserialize(de::value::SeqDeserializer::new(key.into_iter().map(|v| {
+                    super::AnyValueDeserializer::new(v).with_categorical(self.categorical)
+                })))
+            }
+            .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let Some(value) = self.value.take() else {
+            return Err(de::Error::custom("value is missing"));
+        };
+        if let Some(tree) = self.tree {
+            seed.deserialize(
+                super::nested::Deserializer::new(tree, value).with_categorical(self.categorical),
+            )
+        } else {
+            let deserializer = super::RowDeserializer::new(self.names.iter().copied(), value)
+                .with_categorical(self.categorical);
+            match self.key_normalizer {
+                Some(key_normalizer) => {
+                    seed.deserialize(deserializer.with_key_normalizer(key_normalizer))
+                }
+                None => seed.deserialize(deserializer),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+pub struct BorrowedAccess<'t, 'de> {
+    names: &'t [&'de str],
+    iter: vec::IntoIter<(Vec<AnyValue<'de>>, Row<'de>)>,
+    value: Option<Row<'de>>,
+    tree: Option<&'t super::nested::Tree<'de>>,
+    key_normalizer: Option<super::KeyNormalizer<'de>>,
+    categorical: super::CategoricalRepr,
+}
+
+impl<'t, 'de> BorrowedAccess<'t, 'de> {
+    pub fn new(
+        names: &'t [&'de str],
+        rows: Rows<'de>,
+        tree: Option<&'t super::nested::Tree<'de>>,
+        key_normalizer: Option<super::KeyNormalizer<'de>>,
+        categorical: super::CategoricalRepr,
+    ) -> Self {
+        Self {
+            names,
+            iter: rows.into_iter(),
+            value: None,
+            tree,
+            key_normalizer,
+            categorical,
+        }
+    }
+}
+
+impl<'t, 'de> de::MapAccess<'de> for BorrowedAccess<'t, 'de> {
+    type Error = super::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        if let Some((mut key, value)) = self.iter.next() {
+            self.value = Some(value);
+            if key.len() == 1 {
+                seed.deserialize(
+                    super::BorrowedAnyValueDeserializer::new(key.remove(0))
+                        .with_categorical(self.categorical),
+                )
+            } else {
+                seed.deserialize(de::value::SeqDeserializer::new(key.into_iter().map(|v| {
+                    super::BorrowedAnyValueDeserializer::new(v).with_categorical(self.categorical)
+                })))
+            }
+            .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let Some(value) = self.value.take() else {
+            return Err(de::Error::custom("value is missing"));
+        };
+        if let Some(tree) = self.tree {
+            seed.deserialize(
+                super::nested::BorrowedDeserializer::new(tree, value)
+                    .with_categorical(self.categorical),
+            )
+        } else {
+            let deserializer =
+                super::BorrowedRowDeserializer::new(self.names.iter().copied(), value)
+                    .with_categorical(self.categorical);
+            match self.key_normalizer {
+                Some(key_normalizer) => {
+                    seed.deserialize(deserializer.with_key_normalizer(key_normalizer))
+                }
+                None => seed.deserialize(deserializer),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
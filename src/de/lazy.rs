@@ -0,0 +1,123 @@
+use polars_core::frame::DataFrame;
+use polars_lazy::frame::LazyFrame;
+use polars_lazy::prelude::{Expr, col};
+use serde::de;
+
+/// Collects a `LazyFrame` after selecting only the columns read by the target type,
+/// so that the projection is pushed down into the scan.
+///
+/// Targets that are not structs, such as maps, read every column.
+pub struct LazyFrameDeserializer(LazyFrame);
+
+impl LazyFrameDeserializer {
+    pub fn new(value: LazyFrame) -> Self {
+        Self(value)
+    }
+
+    /// Deserializes each row as a `T`.
+    #[cfg(feature = "rows")]
+    pub fn rows<T>(self) -> Result<Vec<T>, super::Error>
+    where
+        T: de::DeserializeOwned,
+    {
+        de::Deserialize::deserialize(super::DataFrameDeserializer::rows(&self.collect::<T>()?))
+    }
+
+    /// Deserializes the frame as a `T` whose fields are columns.
+    pub fn columns<T>(self) -> Result<T, super::Error>
+    where
+        T: de::DeserializeOwned,
+    {
+        T::deserialize(super::DataFrameDeserializer::columns(&self.collect::<T>()?))
+    }
+
+    fn collect<T>(mut self) -> Result<DataFrame, super::Error>
+    where
+        T: de::DeserializeOwned,
+    {
+        let Ok(fields) = super::field_names::<T>() else {
+            return Ok(self.0.collect()?);
+        };
+        // fields missing from the frame are left to `#[serde(default)]`
+        let schema = self.0.collect_schema()?;
+        let columns = fields
+            .iter()
+            .filter(|field| schema.contains(field))
+            .map(|field| col(*field))
+            .collect::<Vec<Expr>>();
+        Ok(self.0.select(columns).collect()?)
+    }
+}
+
+/// Deserializes each row of `value` as a `T`, reading only the columns that `T` needs.
+#[cfg(feature = "rows")]
+pub fn from_lazy<T>(value: LazyFrame) -> Result<Vec<T>, super::Error>
+where
+    T: de::DeserializeOwned,
+{
+    LazyFrameDeserializer::new(value).rows()
+}
+
+#[cfg(test)]
+mod tests {
+    use polars_core::frame::DataFrame;
+    use polars_core::frame::column::Column;
+    use polars_lazy::frame::IntoLazy;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    // `deny_unknown_fields` fails if the other columns were collected
+    #[test]
+    fn test_lazy() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Columns {
+            #[serde(rename = "Ocean")]
+            ocean: Vec<String>,
+            #[serde(default)]
+            depth: Vec<f64>,
+        }
+
+        let s1 = Column::new("Ocean".into(), ["Atlantic", "Indian"]);
+        let s2 = Column::new("Area (km²)".into(), [106_460_000_u64, 70_560_000]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        assert_eq!(
+            super::LazyFrameDeserializer::new(df.clone().lazy())
+                .columns::<Columns>()
+                .unwrap(),
+            Columns {
+                ocean: vec!["Atlantic".to_owned(), "Indian".to_owned()],
+                depth: Vec::new(),
+            },
+        );
+
+        let columns = super::LazyFrameDeserializer::new(df.clone().lazy())
+            .columns::<HashMap<String, Vec<crate::Value>>>()
+            .unwrap();
+        assert_eq!(columns.len(), 2);
+
+        #[cfg(feature = "rows")]
+        {
+            use polars_lazy::prelude::{col, lit};
+
+            #[derive(Debug, PartialEq, Deserialize)]
+            #[serde(deny_unknown_fields)]
+            struct Row {
+                #[serde(rename = "Ocean")]
+                ocean: String,
+            }
+
+            let rows = super::from_lazy::<Row>(
+                df.lazy().filter(col("Area (km²)").gt(lit(100_000_000_u64))),
+            )
+            .unwrap();
+            assert_eq!(
+                rows,
+                [Row {
+                    ocean: "Atlantic".to_owned(),
+                }],
+            );
+        }
+    }
+}
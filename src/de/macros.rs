@@ -1,124 +1,179 @@
 macro_rules! deserialize_delegate {
     () => {
+        deserialize_delegate!(@ except deserialize_struct);
+        deserialize_delegate!(@ deserialize_struct);
+    };
+    // for deserializers that match column names against the fields of a struct
+    (@ except deserialize_struct) => {
+        deserialize_delegate! {
+            deserialize_bool
+            deserialize_i8
+            deserialize_i16
+            deserialize_i32
+            deserialize_i64
+            deserialize_u8
+            deserialize_u16
+            deserialize_u32
+            deserialize_u64
+            deserialize_f32
+            deserialize_f64
+            deserialize_char
+            deserialize_str
+            deserialize_string
+            deserialize_bytes
+            deserialize_byte_buf
+            deserialize_unit
+            deserialize_unit_struct
+            deserialize_newtype_struct
+            deserialize_seq
+            deserialize_tuple
+            deserialize_tuple_struct
+            deserialize_map
+            deserialize_enum
+            deserialize_identifier
+            deserialize_ignored_any
+        }
+    };
+    ($($method:ident)*) => {
+        $(deserialize_delegate!(@ $method);)*
+    };
+    (@ deserialize_bool) => {
         fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_i8) => {
         fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_i16) => {
         fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_i32) => {
         fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_i64) => {
         fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_u8) => {
         fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_u16) => {
         fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_u32) => {
         fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_u64) => {
         fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_f32) => {
         fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_f64) => {
         fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_char) => {
         fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_str) => {
         fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_string) => {
         fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_bytes) => {
         fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_byte_buf) => {
         fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_unit) => {
         fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_unit_struct) => {
         fn deserialize_unit_struct<V>(
             self,
             _: &'static str,
@@ -129,7 +184,9 @@ macro_rules! deserialize_delegate {
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_newtype_struct) => {
+        // the wrapped value is read from the same deserializer
         fn deserialize_newtype_struct<V>(
             self,
             _: &'static str,
@@ -138,23 +195,26 @@ macro_rules! deserialize_delegate {
         where
             V: de::Visitor<'de>,
         {
-            self.deserialize_any(visitor)
+            visitor.visit_newtype_struct(self)
         }
-
+    };
+    (@ deserialize_seq) => {
         fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_tuple) => {
         fn deserialize_tuple<V>(self, _: usize, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_tuple_struct) => {
         fn deserialize_tuple_struct<V>(
             self,
             _: &'static str,
@@ -166,14 +226,16 @@ macro_rules! deserialize_delegate {
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_map) => {
         fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_struct) => {
         fn deserialize_struct<V>(
             self,
             _: &'static str,
@@ -185,7 +247,8 @@ macro_rules! deserialize_delegate {
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_enum) => {
         fn deserialize_enum<V>(
             self,
             _: &'static str,
@@ -197,14 +260,16 @@ macro_rules! deserialize_delegate {
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_identifier) => {
         fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
         {
             self.deserialize_any(visitor)
         }
-
+    };
+    (@ deserialize_ignored_any) => {
         fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: de::Visitor<'de>,
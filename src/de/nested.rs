@@ -0,0 +1,416 @@
+use polars_core::datatypes::AnyValue;
+use polars_core::frame::row::Row;
+use serde::de;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::slice;
+
+/// Column names grouped by a separator, e.g. `address.city` and `address.zip` under `address`.
+pub struct Tree<'n>(
+    Vec<(Cow<'n, str>, Node<'n>)>,
+    Option<super::KeyNormalizer<'n>>,
+);
+
+enum Node<'n> {
+    Leaf(usize),
+    Group(Tree<'n>),
+}
+
+impl<'n> Tree<'n> {
+    pub fn new<I>(
+        column_names: I,
+        separator: Option<&str>,
+        key_normalizer: Option<super::KeyNormalizer<'n>>,
+    ) -> Self
+    where
+        I: IntoIterator<Item = &'n str>,
+    {
+        let mut root = Self(Vec::new(), key_normalizer);
+        for (i, name) in column_names.into_iter().enumerate() {
+            let mut tree = &mut root;
+            let mut keys = match separator {
+                Some(separator) => name.split(separator).collect::<Vec<_>>(),
+                None => vec![name],
+            }
+            .into_iter()
+            .map(|key| key_normalizer.map_or(Cow::Borrowed(key), |n| n.apply(key)))
+            .peekable();
+            while let Some(key) = keys.next() {
+                if keys.peek().is_some() {
+                    tree = tree.group(key);
+                } else {
+                    tree.0.push((key, Node::Leaf(i)));
+                }
+            }
+        }
+        root
+    }
+
+    fn group(&mut self, key: Cow<'n, str>) -> &mut Self {
+        let i = if let Some(i) = self
+            .0
+            .iter()
+            .position(|(k, node)| *k == key && matches!(node, Node::Group(_)))
+        {
+            i
+        } else {
+            self.0.push((key, Node::Group(Self(Vec::new(), self.1))));
+            self.0.len() - 1
+        };
+        match &mut self.0[i].1 {
+            Node::Group(tree) => tree,
+            Node::Leaf(_) => unreachable!(),
+        }
+    }
+
+    fn is_null(&self, values: &[AnyValue<'_>]) -> bool {
+        self.0.iter().all(|(_, node)| match node {
+            Node::Leaf(i) => values[*i].is_null(),
+            Node::Group(tree) => tree.is_null(values),
+        })
+    }
+}
+
+pub struct Deserializer<'t, 'n, 'a>(&'t Tree<'n>, Row<'a>, super::CategoricalRepr);
+
+impl<'t, 'n, 'a> Deserializer<'t, 'n, 'a> {
+    pub fn new(tree: &'t Tree<'n>, value: Row<'a>) -> Self {
+        Self(tree, value, super::CategoricalRepr::default())
+    }
+
+    pub fn with_categorical(self, categorical: super::CategoricalRepr) -> Self {
+        Self(self.0, self.1, categorical)
+    }
+}
+
+impl<'de, 't, 'n, 'a> de::Deserializer<'de> for Deserializer<'t, 'n, 'a> {
+    type Error = super::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        GroupDeserializer(self.0, &RefCell::new(self.1.0), self.2).deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        GroupDeserializer(self.0, &RefCell::new(self.1.0), self.2).visit_fields(fields, visitor)
+    }
+
+    deserialize_delegate!(@ except deserialize_struct);
+}
+
+impl<'de, 't, 'n, 'a> de::IntoDeserializer<'de, super::Error> for Deserializer<'t, 'n, 'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+struct GroupDeserializer<'t, 'n, 'a>(
+    &'t Tree<'n>,
+    &'t RefCell<Vec<AnyValue<'a>>>,
+    super::CategoricalRepr,
+);
+
+impl GroupDeserializer<'_, '_, '_> {
+    fn visit_fields<'de, V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, super::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_map(GroupAccess {
+            iter: self.0.0.iter(),
+            key_normalizer: self.0.1,
+            fields,
+            node: None,
+            values: self.1,
+            categorical: self.2,
+        })
+    }
+}
+
+impl<'de, 't, 'n, 'a> de::Deserializer<'de> for GroupDeserializer<'t, 'n, 'a> {
+    type Error = super::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.visit_fields(&[], visitor)
+    }
+
+    // a group is null when all of its columns are null
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        if self.0.is_null(&self.1.borrow()) {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.visit_fields(fields, visitor)
+    }
+
+    deserialize_delegate!(@ except deserialize_struct);
+}
+
+struct GroupAccess<'t, 'n, 'a> {
+    iter: slice::Iter<'t, (Cow<'n, str>, Node<'n>)>,
+    key_normalizer: Option<super::KeyNormalizer<'n>>,
+    fields: &'static [&'static str],
+    node: Option<&'t Node<'n>>,
+    values: &'t RefCell<Vec<AnyValue<'a>>>,
+    categorical: super::CategoricalRepr,
+}
+
+impl<'de, 't, 'n, 'a> de::MapAccess<'de> for GroupAccess<'t, 'n, 'a> {
+    type Error = super::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        if let Some((key, node)) = self.iter.next() {
+            self.node = Some(node);
+            // the keys of the tree are already normalized
+            let key = self
+                .key_normalizer
+                .and_then(|n| n.field(key, self.fields))
+                .unwrap_or(key);
+            seed.deserialize(de::value::StrDeserializer::new(key))
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        match self.node.take() {
+            Some(Node::Leaf(i)) => {
+                let value = std::mem::take(&mut self.values.borrow_mut()[*i]);
+                seed.deserialize(
+                    super::AnyValueDeserializer::new(value).with_categorical(self.categorical),
+                )
+            }
+            Some(Node::Group(tree)) => {
+                seed.deserialize(GroupDeserializer(tree, self.values, self.categorical))
+            }
+            None => Err(de::Error::custom("value is missing")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+pub struct BorrowedDeserializer<'t, 'de>(&'t Tree<'de>, Row<'de>, super::CategoricalRepr);
+
+impl<'t, 'de> BorrowedDeserializer<'t, 'de> {
+    pub fn new(tree: &'t Tree<'de>, value: Row<'de>) -> Self {
+        Self(tree, value, super::CategoricalRepr::default())
+    }
+
+    pub fn with_categorical(self, categorical: super::CategoricalRepr) -> Self {
+        Self(self.0, self.1, categorical)
+    }
+}
+
+impl<'t, 'de> de::Deserializer<'de> for BorrowedDeserializer<'t, 'de> {
+    type Error = super::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        BorrowedGroupDeserializer(self.0, &RefCell::new(self.1.0), self.2).deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        BorrowedGroupDeserializer(self.0, &RefCell::new(self.1.0), self.2)
+            .visit_fields(fields, visitor)
+    }
+
+    deserialize_delegate!(@ except deserialize_struct);
+}
+
+impl<'t, 'de> de::IntoDeserializer<'de, super::Error> for BorrowedDeserializer<'t, 'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+struct BorrowedGroupDeserializer<'t, 'de>(
+    &'t Tree<'de>,
+    &'t RefCell<Vec<AnyValue<'de>>>,
+    super::CategoricalRepr,
+);
+
+impl<'de> BorrowedGroupDeserializer<'_, 'de> {
+    fn visit_fields<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, super::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_map(BorrowedGroupAccess {
+            iter: self.0.0.iter(),
+            key_normalizer: self.0.1,
+            fields,
+            node: None,
+            values: self.1,
+            categorical: self.2,
+        })
+    }
+}
+
+impl<'t, 'de> de::Deserializer<'de> for BorrowedGroupDeserializer<'t, 'de> {
+    type Error = super::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.visit_fields(&[], visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        if self.0.is_null(&self.1.borrow()) {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.visit_fields(fields, visitor)
+    }
+
+    deserialize_delegate!(@ except deserialize_struct);
+}
+
+struct BorrowedGroupAccess<'t, 'de> {
+    iter: slice::Iter<'t, (Cow<'de, str>, Node<'de>)>,
+    key_normalizer: Option<super::KeyNormalizer<'de>>,
+    fields: &'static [&'static str],
+    node: Option<&'t Node<'de>>,
+    values: &'t RefCell<Vec<AnyValue<'de>>>,
+    categorical: super::CategoricalRepr,
+}
+
+impl<'t, 'de> de::MapAccess<'de> for BorrowedGroupAccess<'t, 'de> {
+    type Error = super::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        if let Some((key, node)) = self.iter.next() {
+            self.node = Some(node);
+            if let Some(field) = self.key_normalizer.and_then(|n| n.field(key, self.fields)) {
+                return seed
+                    .deserialize(de::value::BorrowedStrDeserializer::new(field))
+                    .map(Some);
+            }
+            match key {
+                Cow::Borrowed(key) => {
+                    seed.deserialize(de::value::BorrowedStrDeserializer::new(key))
+                }
+                Cow::Owned(key) => seed.deserialize(de::value::StrDeserializer::new(key)),
+            }
+            .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        match self.node.take() {
+            Some(Node::Leaf(i)) => {
+                let value = std::mem::take(&mut self.values.borrow_mut()[*i]);
+                seed.deserialize(
+                    super::BorrowedAnyValueDeserializer::new(value)
+                        .with_categorical(self.categorical),
+                )
+            }
+            Some(Node::Group(tree)) => seed.deserialize(BorrowedGroupDeserializer(
+                tree,
+                self.values,
+                self.categorical,
+            )),
+            None => Err(de::Error::custom("value is missing")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
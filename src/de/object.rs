@@ -0,0 +1,204 @@
+use crate::Value;
+use polars_core::chunked_array::object::{PolarsObject, PolarsObjectSafe};
+use serde::de;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{LazyLock, PoisonError, RwLock};
+
+type Convert = fn(&dyn PolarsObjectSafe) -> Result<Value, super::Error>;
+
+static REGISTRY: LazyLock<RwLock<HashMap<TypeId, Convert>>> = LazyLock::new(Default::default);
+
+/// Registers `T` so that `Object` values holding it are deserialized through its `IntoDeserializer`.
+///
+/// The registry is global to the process, and registering `T` again replaces its converter.
+pub fn register_object<T>()
+where
+    T: PolarsObject + for<'de> de::IntoDeserializer<'de, super::Error>,
+{
+    REGISTRY
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(TypeId::of::<T>(), convert::<T>);
+}
+
+fn convert<T>(value: &dyn PolarsObjectSafe) -> Result<Value, super::Error>
+where
+    T: PolarsObject + for<'de> de::IntoDeserializer<'de, super::Error>,
+{
+    let Some(value) = value.as_any().downcast_ref::<T>() else {
+        return Err(super::Error::UnregisteredObject(value.type_name()));
+    };
+    de::Deserialize::deserialize(value.clone().into_deserializer())
+}
+
+pub(crate) fn to_value(value: &dyn PolarsObjectSafe) -> Result<Value, super::Error> {
+    // `Any::type_id` on the trait object itself, not on the reference
+    let convert = REGISTRY
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&Any::type_id(value.as_any()))
+        .copied();
+    match convert {
+        Some(convert) => convert(value),
+        None => Err(super::Error::UnregisteredObject(value.type_name())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+    use polars_core::chunked_array::object::PolarsObject;
+    use polars_core::datatypes::ObjectChunked;
+    use polars_core::frame::DataFrame;
+    use polars_core::prelude::IntoColumn;
+    use polars_utils::total_ord::{TotalEq, TotalHash};
+    use serde::Deserialize;
+    use std::collections::HashMap;
+    use std::fmt;
+    use std::hash::{Hash, Hasher};
+
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+    struct Point(i64, i64);
+
+    impl fmt::Display for Point {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "({}, {})", self.0, self.1)
+        }
+    }
+
+    impl TotalEq for Point {
+        fn tot_eq(&self, other: &Self) -> bool {
+            self == other
+        }
+    }
+
+    impl TotalHash for Point {
+        fn tot_hash<H>(&self, state: &mut H)
+        where
+            H: Hasher,
+        {
+            self.hash(state);
+        }
+    }
+
+    impl PolarsObject for Point {
+        fn type_name() -> &'static str {
+            "point"
+        }
+    }
+
+    impl<'de> serde::de::IntoDeserializer<'de, crate::de::Error> for Point {
+        type Deserializer = Value;
+
+        fn into_deserializer(self) -> Self::Deserializer {
+            Value::List(vec![Value::Int64(self.0), Value::Int64(self.1)])
+        }
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+    struct Unregistered(u8);
+
+    impl fmt::Display for Unregistered {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("unregistered")
+        }
+    }
+
+    impl TotalEq for Unregistered {
+        fn tot_eq(&self, _: &Self) -> bool {
+            true
+        }
+    }
+
+    impl TotalHash for Unregistered {
+        fn tot_hash<H>(&self, _: &mut H)
+        where
+            H: Hasher,
+        {
+        }
+    }
+
+    impl PolarsObject for Unregistered {
+        fn type_name() -> &'static str {
+            "unregistered"
+        }
+    }
+
+    #[test]
+    fn test_object() {
+        super::register_object::<Point>();
+
+        let df = DataFrame::new_infer_height(vec![
+            ObjectChunked::new_from_vec("point".into(), vec![Point(1, 2), Point(3, 4)])
+                .into_column(),
+        ])
+        .unwrap();
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Columns {
+            point: Vec<(i64, i64)>,
+        }
+
+        assert_eq!(
+            Columns::deserialize(crate::de::DataFrameDeserializer::columns(&df)).unwrap(),
+            Columns {
+                point: vec![(1, 2), (3, 4)],
+            },
+        );
+
+        #[cfg(feature = "rows")]
+        {
+            #[derive(Debug, PartialEq, Deserialize)]
+            struct Row {
+                point: [i64; 2],
+            }
+
+            assert_eq!(
+                Vec::<Row>::deserialize(crate::de::BorrowedDataFrameDeserializer::rows(&df))
+                    .unwrap(),
+                [Row { point: [1, 2] }, Row { point: [3, 4] }],
+            );
+        }
+
+        assert_eq!(
+            serde_json::to_string(&crate::ser::SerializeDataFrame::rows(&df)).unwrap(),
+            r#"[{"point":[1,2]},{"point":[3,4]}]"#,
+        );
+        assert_eq!(
+            serde_json::to_string(&crate::ser::SerializeDataFrame::columns(&df)).unwrap(),
+            r#"{"point":[[1,2],[3,4]]}"#,
+        );
+    }
+
+    #[test]
+    fn test_poisoned_registry() {
+        let _ = std::thread::spawn(|| {
+            let _guard = super::REGISTRY.write();
+            panic!("converter panicked");
+        })
+        .join();
+        assert!(super::REGISTRY.is_poisoned());
+
+        super::register_object::<Point>();
+        assert_eq!(
+            super::to_value(&Point(1, 2)).unwrap(),
+            Value::List(vec![Value::Int64(1), Value::Int64(2)]),
+        );
+    }
+
+    #[test]
+    fn test_unregistered_object() {
+        let df = DataFrame::new_infer_height(vec![
+            ObjectChunked::new_from_vec("value".into(), vec![Unregistered(0)]).into_column(),
+        ])
+        .unwrap();
+
+        assert!(matches!(
+            HashMap::<String, Vec<Value>>::deserialize(crate::de::DataFrameDeserializer::columns(
+                &df
+            )),
+            Err(crate::de::Error::UnregisteredObject("unregistered")),
+        ));
+    }
+}
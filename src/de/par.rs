@@ -0,0 +1,96 @@
+use serde::de;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+/// Targets of [`par_columns`](super::DataFrameDeserializer::par_columns), whose columns are
+/// deserialized on their own rayon threads the same way as in
+/// [`columns`](super::DataFrameDeserializer::columns) mode.
+///
+/// Maps take every column by its name, and tuples take the columns in frame order.
+pub trait ParColumns<'de>: Sized {
+    #[doc(hidden)]
+    fn par_columns(deserializer: &super::DataFrameDeserializer<'_>) -> Result<Self, super::Error>;
+}
+
+fn columns<'de, C>(deserializer: &super::DataFrameDeserializer<'_>) -> Result<Vec<C>, super::Error>
+where
+    C: de::Deserialize<'de> + Send,
+{
+    use rayon::prelude::*;
+
+    (0..deserializer.width())
+        .into_par_iter()
+        .map(|index| deserializer.column(index))
+        .collect()
+}
+
+impl<'de, K, C, S> ParColumns<'de> for HashMap<K, C, S>
+where
+    K: de::Deserialize<'de> + Eq + Hash,
+    C: de::Deserialize<'de> + Send,
+    S: BuildHasher + Default,
+{
+    fn par_columns(deserializer: &super::DataFrameDeserializer<'_>) -> Result<Self, super::Error> {
+        columns(deserializer)?
+            .into_iter()
+            .enumerate()
+            .map(|(index, column)| Ok((deserializer.key(index)?, column)))
+            .collect()
+    }
+}
+
+impl<'de, K, C> ParColumns<'de> for BTreeMap<K, C>
+where
+    K: de::Deserialize<'de> + Ord,
+    C: de::Deserialize<'de> + Send,
+{
+    fn par_columns(deserializer: &super::DataFrameDeserializer<'_>) -> Result<Self, super::Error> {
+        columns(deserializer)?
+            .into_iter()
+            .enumerate()
+            .map(|(index, column)| Ok((deserializer.key(index)?, column)))
+            .collect()
+    }
+}
+
+macro_rules! par_columns_tuple {
+    ($len:literal => $($name:ident $index:tt)*) => {
+        impl<'de, $($name),*> ParColumns<'de> for ($($name,)*)
+        where
+            $($name: de::Deserialize<'de> + Send,)*
+        {
+            fn par_columns(
+                deserializer: &super::DataFrameDeserializer<'_>,
+            ) -> Result<Self, super::Error> {
+                if deserializer.width() != $len {
+                    return Err(de::Error::invalid_length(
+                        deserializer.width(),
+                        &concat!($len, " columns"),
+                    ));
+                }
+                let mut columns = ($(None::<Result<$name, super::Error>>,)*);
+                rayon::scope(|scope| {
+                    $(
+                        let column = &mut columns.$index;
+                        scope.spawn(move |_| *column = Some(deserializer.column($index)));
+                    )*
+                });
+                // every slot is filled once the scope has ended
+                Ok(($(columns.$index.unwrap()?,)*))
+            }
+        }
+    };
+}
+
+par_columns_tuple!(1 => A 0);
+par_columns_tuple!(2 => A 0 B 1);
+par_columns_tuple!(3 => A 0 B 1 C 2);
+par_columns_tuple!(4 => A 0 B 1 C 2 D 3);
+par_columns_tuple!(5 => A 0 B 1 C 2 D 3 E 4);
+par_columns_tuple!(6 => A 0 B 1 C 2 D 3 E 4 F 5);
+par_columns_tuple!(7 => A 0 B 1 C 2 D 3 E 4 F 5 G 6);
+par_columns_tuple!(8 => A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7);
+par_columns_tuple!(9 => A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8);
+par_columns_tuple!(10 => A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9);
+par_columns_tuple!(11 => A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 K 10);
+par_columns_tuple!(12 => A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 K 10 L 11);
@@ -1,11 +1,24 @@
 use polars_core::frame::row::Row;
 use serde::de;
 
-pub struct Deserializer<'a, I>(I, Row<'a>);
+pub struct Deserializer<'a, I>(
+    I,
+    Row<'a>,
+    Option<super::KeyNormalizer<'a>>,
+    super::CategoricalRepr,
+);
 
 impl<'a, I> Deserializer<'a, I> {
     pub fn new(column_names: I, value: Row<'a>) -> Self {
-        Self(column_names, value)
+        Self(column_names, value, None, super::CategoricalRepr::default())
+    }
+
+    pub fn with_key_normalizer(self, key_normalizer: super::KeyNormalizer<'a>) -> Self {
+        Self(self.0, self.1, Some(key_normalizer), self.3)
+    }
+
+    pub fn with_categorical(self, categorical: super::CategoricalRepr) -> Self {
+        Self(self.0, self.1, self.2, categorical)
     }
 }
 
@@ -19,14 +32,7 @@ where
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_map(de::value::MapDeserializer::new(
-            self.0.into_iter().zip(self.1.0).map(|(k, v)| {
-                (
-                    de::value::StrDeserializer::new(k),
-                    super::AnyValueDeserializer::new(v),
-                )
-            }),
-        ))
+        self.deserialize_struct("", &[], visitor)
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -36,7 +42,26 @@ where
         self.deserialize_any(visitor)
     }
 
-    deserialize_delegate!();
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_map(de::value::MapDeserializer::new(
+            self.0.into_iter().zip(self.1.0).map(|(k, v)| {
+                (
+                    super::key::Deserializer::new(super::key::normalize(self.2, k, fields)),
+                    super::AnyValueDeserializer::new(v).with_categorical(self.3),
+                )
+            }),
+        ))
+    }
+
+    deserialize_delegate!(@ except deserialize_struct);
 }
 
 impl<'de, 'a, 'b, I> de::IntoDeserializer<'de, super::Error> for Deserializer<'a, I>
@@ -50,11 +75,24 @@ where
     }
 }
 
-pub struct BorrowedDeserializer<'de, I>(I, Row<'de>);
+pub struct BorrowedDeserializer<'de, I>(
+    I,
+    Row<'de>,
+    Option<super::KeyNormalizer<'de>>,
+    super::CategoricalRepr,
+);
 
 impl<'de, I> BorrowedDeserializer<'de, I> {
     pub fn new(column_names: I, value: Row<'de>) -> Self {
-        Self(column_names, value)
+        Self(column_names, value, None, super::CategoricalRepr::default())
+    }
+
+    pub fn with_key_normalizer(self, key_normalizer: super::KeyNormalizer<'de>) -> Self {
+        Self(self.0, self.1, Some(key_normalizer), self.3)
+    }
+
+    pub fn with_categorical(self, categorical: super::CategoricalRepr) -> Self {
+        Self(self.0, self.1, self.2, categorical)
     }
 }
 
@@ -68,14 +106,7 @@ where
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_map(de::value::MapDeserializer::new(
-            self.0.into_iter().zip(self.1.0).map(|(k, v)| {
-                (
-                    de::value::BorrowedStrDeserializer::new(k),
-                    super::BorrowedAnyValueDeserializer::new(v),
-                )
-            }),
-        ))
+        self.deserialize_struct("", &[], visitor)
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -85,7 +116,26 @@ where
         self.deserialize_any(visitor)
     }
 
-    deserialize_delegate!();
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_map(de::value::MapDeserializer::new(
+            self.0.into_iter().zip(self.1.0).map(|(k, v)| {
+                (
+                    super::key::BorrowedDeserializer::new(super::key::normalize(self.2, k, fields)),
+                    super::BorrowedAnyValueDeserializer::new(v).with_categorical(self.3),
+                )
+            }),
+        ))
+    }
+
+    deserialize_delegate!(@ except deserialize_struct);
 }
 
 impl<'de, I> de::IntoDeserializer<'de, super::Error> for BorrowedDeserializer<'de, I>
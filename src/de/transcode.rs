@@ -0,0 +1,54 @@
+use serde::{de, ser};
+
+/// Streams the values of a deserializer such as `BorrowedDataFrameDeserializer::rows(&df)` into `serializer`
+/// without deserializing them into an intermediate type.
+pub fn transcode<'de, D, S>(deserializer: D, serializer: S) -> Result<S::Ok, S::Error>
+where
+    D: de::Deserializer<'de, Error = super::Error>,
+    S: ser::Serializer,
+{
+    serde_transcode::transcode(deserializer, serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use polars_core::frame::DataFrame;
+    use polars_core::frame::column::Column;
+
+    #[test]
+    fn test_transcode() {
+        let s1 = Column::new("Ocean".into(), ["Atlantic", "Indian"]);
+        let s2 = Column::new("Area (km²)".into(), [None, Some(70_560_000_u64)]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        let mut buf = Vec::new();
+        super::transcode(
+            crate::de::BorrowedDataFrameDeserializer::columns(&df),
+            &mut serde_json::Serializer::new(&mut buf),
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            r#"{"Ocean":["Atlantic","Indian"],"Area (km²)":[null,70560000]}"#,
+        );
+    }
+
+    #[cfg(feature = "rows")]
+    #[test]
+    fn test_transcode_rows() {
+        let s1 = Column::new("Ocean".into(), ["Atlantic", "Indian"]);
+        let s2 = Column::new("Area (km²)".into(), [None, Some(70_560_000_u64)]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        let mut buf = Vec::new();
+        super::transcode(
+            crate::de::BorrowedDataFrameDeserializer::rows(&df),
+            &mut serde_json::Serializer::new(&mut buf),
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            r#"[{"Ocean":"Atlantic","Area (km²)":null},{"Ocean":"Indian","Area (km²)":70560000}]"#,
+        );
+    }
+}
@@ -52,3 +52,12 @@
 //! ```
 
 pub mod de;
+pub mod ser;
+mod trace;
+mod value;
+
+pub use de::from_columns;
+#[cfg(feature = "rows")]
+pub use de::from_rows;
+pub use ser::to_data_frame;
+pub use value::Value;
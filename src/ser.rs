@@ -0,0 +1,52 @@
+#[macro_use]
+mod macros;
+mod any_value;
+mod builder;
+#[cfg(feature = "dtype-categorical")]
+mod categorical;
+mod data_frame;
+#[cfg(any(
+    feature = "dtype-categorical",
+    feature = "dtype-date",
+    feature = "dtype-datetime",
+    feature = "dtype-duration",
+    feature = "dtype-time",
+    feature = "dtype-decimal",
+))]
+mod logical;
+mod row;
+
+pub use any_value::SerializeAnyValue;
+#[cfg(feature = "dtype-categorical")]
+pub use categorical::enum_dtype;
+pub use data_frame::{
+    RowsSerializer, SerializeDataFrame, Serializer as DataFrameSerializer, to_data_frame,
+};
+use polars_core::datatypes::DataType;
+use serde::ser;
+use std::fmt;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Polars(#[from] polars_core::error::PolarsError),
+    #[error("unsupported data type {0}")]
+    UnsupportedDataType(DataType),
+    #[error("cannot serialize {1} into a column of type {0}")]
+    Mismatch(DataType, &'static str),
+    #[error("value out of range for {0}")]
+    OutOfRange(DataType),
+    #[error("cannot serialize {0} here")]
+    Unsupported(&'static str),
+    #[error("{0}")]
+    Custom(String),
+}
+
+impl ser::Error for Error {
+    fn custom<T>(m: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self::Custom(m.to_string())
+    }
+}
@@ -0,0 +1,49 @@
+use crate::de::{BorrowedAnyValueDeserializer, CategoricalRepr};
+use polars_core::datatypes::AnyValue;
+use serde::ser;
+
+/// Serializes an `AnyValue` by transcoding what `AnyValueDeserializer` presents, so that both
+/// directions share one mapping.
+pub struct SerializeAnyValue<'a>(&'a AnyValue<'a>, CategoricalRepr);
+
+impl<'a> SerializeAnyValue<'a> {
+    pub fn new(value: &'a AnyValue<'a>) -> Self {
+        Self(value, CategoricalRepr::default())
+    }
+
+    pub fn with_categorical(self, categorical: CategoricalRepr) -> Self {
+        Self(self.0, categorical)
+    }
+}
+
+impl ser::Serialize for SerializeAnyValue<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serde_transcode::transcode(
+            BorrowedAnyValueDeserializer::new(self.0.as_borrowed()).with_categorical(self.1),
+            serializer,
+        )
+    }
+}
+
+// values of a list, array or column are serialized as they are read, without cloning them
+pub(super) fn serialize_values<'a, S>(
+    serializer: S,
+    len: usize,
+    values: impl Iterator<Item = AnyValue<'a>>,
+    categorical: CategoricalRepr,
+) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    let mut seq = serializer.serialize_seq(Some(len))?;
+    for value in values {
+        ser::SerializeSeq::serialize_element(
+            &mut seq,
+            &SerializeAnyValue::new(&value).with_categorical(categorical),
+        )?;
+    }
+    ser::SerializeSeq::end(seq)
+}
@@ -0,0 +1,1100 @@
+use num_traits::{NumCast, ToPrimitive};
+use polars_core::chunked_array::builder::{
+    BinaryChunkedBuilder, BooleanChunkedBuilder, ChunkedBuilder, PrimitiveChunkedBuilder,
+    StringChunkedBuilder,
+};
+#[cfg(feature = "dtype-array")]
+use polars_core::datatypes::ArrayChunked;
+#[cfg(feature = "dtype-f16")]
+use polars_core::datatypes::Float16Type;
+#[cfg(feature = "dtype-i8")]
+use polars_core::datatypes::Int8Type;
+#[cfg(feature = "dtype-i16")]
+use polars_core::datatypes::Int16Type;
+#[cfg(feature = "dtype-i128")]
+use polars_core::datatypes::Int128Type;
+#[cfg(feature = "dtype-u8")]
+use polars_core::datatypes::UInt8Type;
+#[cfg(feature = "dtype-u16")]
+use polars_core::datatypes::UInt16Type;
+#[cfg(feature = "dtype-u128")]
+use polars_core::datatypes::UInt128Type;
+#[cfg(feature = "dtype-categorical")]
+use polars_core::datatypes::{Categories, FrozenCategories};
+use polars_core::datatypes::{
+    DataType, Float32Type, Float64Type, Int32Type, Int64Type, ListChunked, PolarsNumericType,
+    UInt32Type, UInt64Type,
+};
+#[cfg(feature = "dtype-categorical")]
+use polars_core::prelude::PlIndexSet;
+#[cfg(feature = "dtype-struct")]
+use polars_core::prelude::StructChunked;
+use polars_core::prelude::{IntoSeries, PlSmallStr};
+#[cfg(feature = "dtype-struct")]
+use polars_core::schema::Schema;
+use polars_core::series::Series;
+#[cfg(feature = "dtype-array")]
+use polars_core::utils::arrow::array::FixedSizeListArray;
+use polars_core::utils::arrow::array::ListArray;
+use polars_core::utils::arrow::bitmap::MutableBitmap;
+use polars_core::utils::arrow::offset::Offsets;
+use serde::ser;
+#[cfg(feature = "dtype-categorical")]
+use std::collections::BTreeMap;
+use std::mem;
+
+/// A column under construction.
+///
+/// The variant is chosen from the schema, or from the first non-null value when no schema is given.
+/// Integer columns without a schema are widened to `Int64`, then `Int128` with `dtype-i128`, when a
+/// later value does not fit, and to `Float64` when a float follows.
+pub(super) enum Builder {
+    Null(usize),
+    Boolean(BooleanChunkedBuilder),
+    #[cfg(feature = "dtype-i8")]
+    Int8(PrimitiveChunkedBuilder<Int8Type>),
+    #[cfg(feature = "dtype-i16")]
+    Int16(PrimitiveChunkedBuilder<Int16Type>),
+    Int32(PrimitiveChunkedBuilder<Int32Type>),
+    Int64(PrimitiveChunkedBuilder<Int64Type>),
+    #[cfg(feature = "dtype-i128")]
+    Int128(PrimitiveChunkedBuilder<Int128Type>),
+    #[cfg(feature = "dtype-u8")]
+    UInt8(PrimitiveChunkedBuilder<UInt8Type>),
+    #[cfg(feature = "dtype-u16")]
+    UInt16(PrimitiveChunkedBuilder<UInt16Type>),
+    UInt32(PrimitiveChunkedBuilder<UInt32Type>),
+    UInt64(PrimitiveChunkedBuilder<UInt64Type>),
+    #[cfg(feature = "dtype-u128")]
+    UInt128(PrimitiveChunkedBuilder<UInt128Type>),
+    #[cfg(feature = "dtype-f16")]
+    Float16(PrimitiveChunkedBuilder<Float16Type>),
+    Float32(PrimitiveChunkedBuilder<Float32Type>),
+    Float64(PrimitiveChunkedBuilder<Float64Type>),
+    String(StringChunkedBuilder),
+    Binary(BinaryChunkedBuilder),
+    List(Box<ListBuilder>),
+    #[cfg(feature = "dtype-categorical")]
+    Categorical(Box<CategoricalBuilder>),
+    #[cfg(feature = "dtype-array")]
+    Array(Box<ArrayBuilder>),
+    #[cfg(feature = "dtype-struct")]
+    Struct(Box<StructBuilder>),
+    Inferred(Box<Builder>),
+    /// The physical values of a logical data type.
+    #[cfg(any(
+        feature = "dtype-date",
+        feature = "dtype-datetime",
+        feature = "dtype-duration",
+        feature = "dtype-time",
+        feature = "dtype-decimal",
+    ))]
+    Logical(Box<Builder>, DataType),
+}
+
+impl Builder {
+    pub(super) fn new(dtype: &DataType, capacity: usize) -> Result<Self, super::Error> {
+        Ok(match dtype {
+            DataType::Null => Self::Null(0),
+            DataType::Boolean => {
+                Self::Boolean(BooleanChunkedBuilder::new(PlSmallStr::EMPTY, capacity))
+            }
+            #[cfg(feature = "dtype-i8")]
+            DataType::Int8 => Self::Int8(PrimitiveChunkedBuilder::new(PlSmallStr::EMPTY, capacity)),
+            #[cfg(feature = "dtype-i16")]
+            DataType::Int16 => {
+                Self::Int16(PrimitiveChunkedBuilder::new(PlSmallStr::EMPTY, capacity))
+            }
+            DataType::Int32 => {
+                Self::Int32(PrimitiveChunkedBuilder::new(PlSmallStr::EMPTY, capacity))
+            }
+            DataType::Int64 => {
+                Self::Int64(PrimitiveChunkedBuilder::new(PlSmallStr::EMPTY, capacity))
+            }
+            #[cfg(feature = "dtype-i128")]
+            DataType::Int128 => {
+                Self::Int128(PrimitiveChunkedBuilder::new(PlSmallStr::EMPTY, capacity))
+            }
+            #[cfg(feature = "dtype-u8")]
+            DataType::UInt8 => {
+                Self::UInt8(PrimitiveChunkedBuilder::new(PlSmallStr::EMPTY, capacity))
+            }
+            #[cfg(feature = "dtype-u16")]
+            DataType::UInt16 => {
+                Self::UInt16(PrimitiveChunkedBuilder::new(PlSmallStr::EMPTY, capacity))
+            }
+            DataType::UInt32 => {
+                Self::UInt32(PrimitiveChunkedBuilder::new(PlSmallStr::EMPTY, capacity))
+            }
+            DataType::UInt64 => {
+                Self::UInt64(PrimitiveChunkedBuilder::new(PlSmallStr::EMPTY, capacity))
+            }
+            #[cfg(feature = "dtype-u128")]
+            DataType::UInt128 => {
+                Self::UInt128(PrimitiveChunkedBuilder::new(PlSmallStr::EMPTY, capacity))
+            }
+            #[cfg(feature = "dtype-f16")]
+            DataType::Float16 => {
+                Self::Float16(PrimitiveChunkedBuilder::new(PlSmallStr::EMPTY, capacity))
+            }
+            DataType::Float32 => {
+                Self::Float32(PrimitiveChunkedBuilder::new(PlSmallStr::EMPTY, capacity))
+            }
+            DataType::Float64 => {
+                Self::Float64(PrimitiveChunkedBuilder::new(PlSmallStr::EMPTY, capacity))
+            }
+            DataType::String => {
+                Self::String(StringChunkedBuilder::new(PlSmallStr::EMPTY, capacity))
+            }
+            DataType::Binary => {
+                Self::Binary(BinaryChunkedBuilder::new(PlSmallStr::EMPTY, capacity))
+            }
+            DataType::List(inner) => Self::List(Box::new(ListBuilder {
+                values: Self::new(inner, 0)?,
+                offsets: Offsets::with_capacity(capacity),
+                validity: MutableBitmap::with_capacity(capacity),
+            })),
+            #[cfg(feature = "dtype-categorical")]
+            DataType::Categorical(_, _) | DataType::Enum(_, _) => {
+                Self::Categorical(Box::new(CategoricalBuilder {
+                    dtype: Some(dtype.clone()),
+                    name: None,
+                    variants: BTreeMap::new(),
+                    labels: PlIndexSet::default(),
+                    values: StringChunkedBuilder::new(PlSmallStr::EMPTY, capacity),
+                }))
+            }
+            #[cfg(feature = "dtype-array")]
+            DataType::Array(inner, width) => Self::Array(Box::new(ArrayBuilder {
+                values: Self::new(inner, capacity * width)?,
+                width: *width,
+                validity: MutableBitmap::with_capacity(capacity),
+            })),
+            #[cfg(feature = "dtype-struct")]
+            DataType::Struct(fields) => Self::Struct(Box::new(StructBuilder {
+                fields: super::row::Columns::new(
+                    Some(&Schema::from_iter(fields.iter().cloned())),
+                    capacity,
+                )?,
+                validity: MutableBitmap::with_capacity(capacity),
+            })),
+            #[cfg(feature = "dtype-date")]
+            DataType::Date => Self::logical(dtype, capacity)?,
+            #[cfg(feature = "dtype-datetime")]
+            DataType::Datetime(_, _) => Self::logical(dtype, capacity)?,
+            #[cfg(feature = "dtype-duration")]
+            DataType::Duration(_) => Self::logical(dtype, capacity)?,
+            #[cfg(feature = "dtype-time")]
+            DataType::Time => Self::logical(dtype, capacity)?,
+            #[cfg(feature = "dtype-decimal")]
+            DataType::Decimal(_, _) => Self::logical(dtype, capacity)?,
+            _ => return Err(super::Error::UnsupportedDataType(dtype.clone())),
+        })
+    }
+
+    #[cfg(any(
+        feature = "dtype-date",
+        feature = "dtype-datetime",
+        feature = "dtype-duration",
+        feature = "dtype-time",
+        feature = "dtype-decimal",
+    ))]
+    fn logical(dtype: &DataType, capacity: usize) -> Result<Self, super::Error> {
+        Ok(Self::Logical(
+            Box::new(Self::new(&dtype.to_physical(), capacity)?),
+            dtype.clone(),
+        ))
+    }
+
+    pub(super) fn dtype(&self) -> DataType {
+        match self {
+            Self::Null(_) => DataType::Null,
+            Self::Boolean(_) => DataType::Boolean,
+            #[cfg(feature = "dtype-i8")]
+            Self::Int8(_) => DataType::Int8,
+            #[cfg(feature = "dtype-i16")]
+            Self::Int16(_) => DataType::Int16,
+            Self::Int32(_) => DataType::Int32,
+            Self::Int64(_) => DataType::Int64,
+            #[cfg(feature = "dtype-i128")]
+            Self::Int128(_) => DataType::Int128,
+            #[cfg(feature = "dtype-u8")]
+            Self::UInt8(_) => DataType::UInt8,
+            #[cfg(feature = "dtype-u16")]
+            Self::UInt16(_) => DataType::UInt16,
+            Self::UInt32(_) => DataType::UInt32,
+            Self::UInt64(_) => DataType::UInt64,
+            #[cfg(feature = "dtype-u128")]
+            Self::UInt128(_) => DataType::UInt128,
+            #[cfg(feature = "dtype-f16")]
+            Self::Float16(_) => DataType::Float16,
+            Self::Float32(_) => DataType::Float32,
+            Self::Float64(_) => DataType::Float64,
+            Self::String(_) => DataType::String,
+            Self::Binary(_) => DataType::Binary,
+            Self::List(v) => DataType::List(Box::new(v.values.dtype())),
+            #[cfg(feature = "dtype-categorical")]
+            Self::Categorical(v) => v.dtype().unwrap_or(DataType::String),
+            #[cfg(feature = "dtype-array")]
+            Self::Array(v) => DataType::Array(Box::new(v.values.dtype()), v.width),
+            #[cfg(feature = "dtype-struct")]
+            Self::Struct(v) => DataType::Struct(v.fields.fields()),
+            Self::Inferred(v) => v.dtype(),
+            #[cfg(any(
+                feature = "dtype-date",
+                feature = "dtype-datetime",
+                feature = "dtype-duration",
+                feature = "dtype-time",
+                feature = "dtype-decimal",
+            ))]
+            Self::Logical(_, dtype) => dtype.clone(),
+        }
+    }
+
+    /// Replaces a pending `Null` builder with a typed one, back-filling the nulls seen so far.
+    fn init(&mut self, dtype: &DataType) -> Result<(), super::Error> {
+        if let Self::Null(len) = *self {
+            let mut builder = Self::new(dtype, len)?;
+            for _ in 0..len {
+                builder.push_null();
+            }
+            *self = builder;
+        }
+        Ok(())
+    }
+
+    pub(super) fn push_null(&mut self) {
+        match self {
+            Self::Null(len) => *len += 1,
+            Self::Boolean(b) => b.append_null(),
+            #[cfg(feature = "dtype-i8")]
+            Self::Int8(b) => b.append_null(),
+            #[cfg(feature = "dtype-i16")]
+            Self::Int16(b) => b.append_null(),
+            Self::Int32(b) => b.append_null(),
+            Self::Int64(b) => b.append_null(),
+            #[cfg(feature = "dtype-i128")]
+            Self::Int128(b) => b.append_null(),
+            #[cfg(feature = "dtype-u8")]
+            Self::UInt8(b) => b.append_null(),
+            #[cfg(feature = "dtype-u16")]
+            Self::UInt16(b) => b.append_null(),
+            Self::UInt32(b) => b.append_null(),
+            Self::UInt64(b) => b.append_null(),
+            #[cfg(feature = "dtype-u128")]
+            Self::UInt128(b) => b.append_null(),
+            #[cfg(feature = "dtype-f16")]
+            Self::Float16(b) => b.append_null(),
+            Self::Float32(b) => b.append_null(),
+            Self::Float64(b) => b.append_null(),
+            Self::String(b) => b.append_null(),
+            Self::Binary(b) => b.append_null(),
+            Self::List(b) => {
+                b.offsets.extend_constant(1);
+                b.validity.push(false);
+            }
+            #[cfg(feature = "dtype-categorical")]
+            Self::Categorical(b) => b.values.append_null(),
+            #[cfg(feature = "dtype-array")]
+            Self::Array(b) => {
+                for _ in 0..b.width {
+                    b.values.push_null();
+                }
+                b.validity.push(false);
+            }
+            #[cfg(feature = "dtype-struct")]
+            Self::Struct(b) => {
+                b.fields.end_row();
+                b.validity.push(false);
+            }
+            Self::Inferred(b) => b.push_null(),
+            #[cfg(any(
+                feature = "dtype-date",
+                feature = "dtype-datetime",
+                feature = "dtype-duration",
+                feature = "dtype-time",
+                feature = "dtype-decimal",
+            ))]
+            Self::Logical(b, _) => b.push_null(),
+        }
+    }
+
+    fn push_bool(&mut self, v: bool) -> Result<(), super::Error> {
+        self.init(&DataType::Boolean)?;
+        match self {
+            Self::Boolean(b) => b.append_value(v),
+            _ => return Err(super::Error::Mismatch(self.dtype(), "bool")),
+        }
+        Ok(())
+    }
+
+    fn push_num<N>(&mut self, v: N, dtype: DataType, name: &'static str) -> Result<(), super::Error>
+    where
+        N: Copy + ToPrimitive,
+    {
+        if let Self::Null(_) = self {
+            self.init(&dtype)?;
+            *self = Self::Inferred(Box::new(mem::replace(self, Self::Null(0))));
+        }
+        if let Self::Inferred(b) = self {
+            loop {
+                match b.push_num_exact(v, dtype.clone(), name) {
+                    Err(e @ (super::Error::OutOfRange(_) | super::Error::Mismatch(_, _))) => {
+                        if !b.widen(dtype.is_float())? {
+                            return Err(e);
+                        }
+                    }
+                    result => return result,
+                }
+            }
+        }
+        self.push_num_exact(v, dtype, name)
+    }
+
+    fn push_num_exact<N>(
+        &mut self,
+        v: N,
+        dtype: DataType,
+        name: &'static str,
+    ) -> Result<(), super::Error>
+    where
+        N: ToPrimitive,
+    {
+        fn cast<N, T>(v: N, dtype: DataType) -> Result<T, super::Error>
+        where
+            N: ToPrimitive,
+            T: NumCast,
+        {
+            T::from(v).ok_or(super::Error::OutOfRange(dtype))
+        }
+
+        self.init(&dtype)?;
+        // floats are never narrowed into integer columns
+        match self {
+            #[cfg(feature = "dtype-i8")]
+            Self::Int8(b) if !dtype.is_float() => b.append_value(cast(v, DataType::Int8)?),
+            #[cfg(feature = "dtype-i16")]
+            Self::Int16(b) if !dtype.is_float() => b.append_value(cast(v, DataType::Int16)?),
+            Self::Int32(b) if !dtype.is_float() => b.append_value(cast(v, DataType::Int32)?),
+            Self::Int64(b) if !dtype.is_float() => b.append_value(cast(v, DataType::Int64)?),
+            #[cfg(feature = "dtype-i128")]
+            Self::Int128(b) if !dtype.is_float() => b.append_value(cast(v, DataType::Int128)?),
+            #[cfg(feature = "dtype-u8")]
+            Self::UInt8(b) if !dtype.is_float() => b.append_value(cast(v, DataType::UInt8)?),
+            #[cfg(feature = "dtype-u16")]
+            Self::UInt16(b) if !dtype.is_float() => b.append_value(cast(v, DataType::UInt16)?),
+            Self::UInt32(b) if !dtype.is_float() => b.append_value(cast(v, DataType::UInt32)?),
+            Self::UInt64(b) if !dtype.is_float() => b.append_value(cast(v, DataType::UInt64)?),
+            #[cfg(feature = "dtype-u128")]
+            Self::UInt128(b) if !dtype.is_float() => b.append_value(cast(v, DataType::UInt128)?),
+            #[cfg(feature = "dtype-f16")]
+            Self::Float16(b) => b.append_value(cast(v, DataType::Float16)?),
+            Self::Float32(b) => b.append_value(cast(v, DataType::Float32)?),
+            Self::Float64(b) => b.append_value(cast(v, DataType::Float64)?),
+            #[cfg(any(
+                feature = "dtype-date",
+                feature = "dtype-datetime",
+                feature = "dtype-duration",
+                feature = "dtype-time",
+                feature = "dtype-decimal",
+            ))]
+            Self::Logical(b, _) => return b.push_num_exact(v, dtype, name),
+            _ => return Err(super::Error::Mismatch(self.dtype(), name)),
+        }
+        Ok(())
+    }
+
+    // integers become floats only when a float follows, so an integer that fits no wider integer
+    // type is an error rather than a rounded float
+    fn widen(&mut self, float: bool) -> Result<bool, super::Error> {
+        fn rebuild<T>(series: &Series) -> Result<PrimitiveChunkedBuilder<T>, super::Error>
+        where
+            T: PolarsNumericType,
+        {
+            let values = series.unpack::<T>()?;
+            let mut b = PrimitiveChunkedBuilder::new(PlSmallStr::EMPTY, values.len());
+            values.iter().for_each(|v| b.append_option(v));
+            Ok(b)
+        }
+
+        let targets = if float {
+            vec![DataType::Float64]
+        } else {
+            vec![
+                DataType::Int64,
+                #[cfg(feature = "dtype-i128")]
+                DataType::Int128,
+            ]
+        };
+        let dtype = self.dtype();
+        let start = targets
+            .iter()
+            .position(|target| *target == dtype)
+            .map_or(0, |i| i + 1);
+        if start == targets.len() {
+            return Ok(false);
+        }
+        let series = mem::replace(self, Self::Null(0)).finish()?;
+        let Some(series) = targets[start..]
+            .iter()
+            .find_map(|target| series.strict_cast(target).ok())
+        else {
+            return Ok(false);
+        };
+        *self = match series.dtype() {
+            DataType::Int64 => Self::Int64(rebuild(&series)?),
+            #[cfg(feature = "dtype-i128")]
+            DataType::Int128 => Self::Int128(rebuild(&series)?),
+            _ => Self::Float64(rebuild(&series)?),
+        };
+        Ok(true)
+    }
+
+    fn push_str(&mut self, v: &str) -> Result<(), super::Error> {
+        self.init(&DataType::String)?;
+        match self {
+            Self::String(b) => b.append_value(v),
+            #[cfg(feature = "dtype-categorical")]
+            Self::Categorical(b) => b.values.append_value(v),
+            // decimals are read as exact strings
+            #[cfg(feature = "dtype-decimal")]
+            Self::Logical(b, DataType::Decimal(_, scale)) => {
+                let Some(v) = crate::value::parse_decimal(v, *scale) else {
+                    return Err(ser::Error::custom(format_args!(
+                        "{v:?} is not a decimal with scale {scale}",
+                    )));
+                };
+                return b.push_num_exact(v, DataType::Int128, "str");
+            }
+            _ => return Err(super::Error::Mismatch(self.dtype(), "str")),
+        }
+        Ok(())
+    }
+
+    // unit variants and `Enum` labels without a schema become an `Enum` of the labels seen
+    #[cfg(feature = "dtype-categorical")]
+    fn init_enum(&mut self) {
+        if let Self::Null(len) = *self {
+            let mut builder = Self::Categorical(Box::new(CategoricalBuilder {
+                dtype: None,
+                name: None,
+                variants: BTreeMap::new(),
+                labels: PlIndexSet::default(),
+                values: StringChunkedBuilder::new(PlSmallStr::EMPTY, len),
+            }));
+            for _ in 0..len {
+                builder.push_null();
+            }
+            *self = builder;
+        }
+    }
+
+    #[cfg(feature = "dtype-categorical")]
+    fn push_label(&mut self, v: &str, dtype: Option<DataType>) -> Result<(), super::Error> {
+        match dtype {
+            Some(dtype) => self.init(&dtype)?,
+            None => self.init_enum(),
+        }
+        match self {
+            Self::Categorical(b) if b.dtype.is_none() => {
+                b.labels.insert(v.into());
+                b.values.append_value(v);
+            }
+            _ => self.push_str(v)?,
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "dtype-categorical")]
+    fn push_variant(
+        &mut self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+    ) -> Result<(), super::Error> {
+        self.init_enum();
+        match self {
+            Self::String(b) => b.append_value(variant),
+            Self::Categorical(b) => {
+                b.name.get_or_insert(name);
+                b.variants.insert(index, variant);
+                b.values.append_value(variant);
+            }
+            _ => return Err(super::Error::Mismatch(self.dtype(), "unit variant")),
+        }
+        Ok(())
+    }
+
+    #[cfg(any(
+        feature = "dtype-date",
+        feature = "dtype-datetime",
+        feature = "dtype-duration",
+        feature = "dtype-time",
+        feature = "dtype-decimal",
+    ))]
+    fn push_logical(
+        &mut self,
+        dtype: &DataType,
+        physical: crate::Value,
+    ) -> Result<(), super::Error> {
+        self.init(dtype)?;
+        match self {
+            Self::Logical(b, d) if d == dtype => {
+                ser::Serialize::serialize(&physical, ValueSerializer(b))
+            }
+            _ => Err(super::Error::Mismatch(self.dtype(), "logical value")),
+        }
+    }
+
+    fn push_bytes(&mut self, v: &[u8]) -> Result<(), super::Error> {
+        self.init(&DataType::Binary)?;
+        match self {
+            Self::Binary(b) => b.append_value(v),
+            _ => return Err(super::Error::Mismatch(self.dtype(), "bytes")),
+        }
+        Ok(())
+    }
+
+    fn seq(
+        &mut self,
+        dtype: DataType,
+        name: &'static str,
+    ) -> Result<SeqSerializer<'_>, super::Error> {
+        self.init(&dtype)?;
+        match (self, dtype) {
+            (Self::List(b), DataType::List(_)) => Ok(SeqSerializer::List(b, 0)),
+            #[cfg(feature = "dtype-array")]
+            (Self::Array(b), DataType::Array(_, width)) => {
+                if b.width == width {
+                    Ok(SeqSerializer::Array(b))
+                } else {
+                    Err(super::Error::Mismatch(
+                        DataType::Array(Box::new(b.values.dtype()), b.width),
+                        name,
+                    ))
+                }
+            }
+            (builder, _) => Err(super::Error::Mismatch(builder.dtype(), name)),
+        }
+    }
+
+    #[cfg(feature = "dtype-struct")]
+    fn fields(&mut self, name: &'static str) -> Result<&mut super::row::Columns, super::Error> {
+        self.init(&DataType::Struct(Vec::new()))?;
+        match self {
+            Self::Struct(b) => {
+                b.validity.push(true);
+                Ok(&mut b.fields)
+            }
+            _ => Err(super::Error::Mismatch(self.dtype(), name)),
+        }
+    }
+
+    /// Completes the `Enum` categories of unit variants seen without a schema with the traced
+    /// `variants` of their enum.
+    #[cfg(feature = "dtype-categorical")]
+    pub(super) fn add_variants(&mut self, variants: &crate::trace::Variants) {
+        match self {
+            Self::List(b) => b.values.add_variants(variants),
+            #[cfg(feature = "dtype-array")]
+            Self::Array(b) => b.values.add_variants(variants),
+            #[cfg(feature = "dtype-struct")]
+            Self::Struct(b) => b.fields.add_variants(variants),
+            Self::Inferred(b) => b.add_variants(variants),
+            Self::Categorical(b) if b.dtype.is_none() => {
+                let Some(traced) = b.name.and_then(|name| variants.get(name)) else {
+                    return;
+                };
+                // another enum of the same name is left alone
+                if b.variants
+                    .iter()
+                    .all(|(index, variant)| traced.get(*index as usize) == Some(variant))
+                {
+                    b.variants.extend((0..).zip(traced.iter().copied()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub(super) fn finish(self) -> Result<Series, super::Error> {
+        Ok(match self {
+            Self::Null(len) => Series::full_null(PlSmallStr::EMPTY, len, &DataType::Null),
+            Self::Boolean(b) => b.finish().into_series(),
+            #[cfg(feature = "dtype-i8")]
+            Self::Int8(b) => b.finish().into_series(),
+            #[cfg(feature = "dtype-i16")]
+            Self::Int16(b) => b.finish().into_series(),
+            Self::Int32(b) => b.finish().into_series(),
+            Self::Int64(b) => b.finish().into_series(),
+            #[cfg(feature = "dtype-i128")]
+            Self::Int128(b) => b.finish().into_series(),
+            #[cfg(feature = "dtype-u8")]
+            Self::UInt8(b) => b.finish().into_series(),
+            #[cfg(feature = "dtype-u16")]
+            Self::UInt16(b) => b.finish().into_series(),
+            Self::UInt32(b) => b.finish().into_series(),
+            Self::UInt64(b) => b.finish().into_series(),
+            #[cfg(feature = "dtype-u128")]
+            Self::UInt128(b) => b.finish().into_series(),
+            #[cfg(feature = "dtype-f16")]
+            Self::Float16(b) => b.finish().into_series(),
+            Self::Float32(b) => b.finish().into_series(),
+            Self::Float64(b) => b.finish().into_series(),
+            Self::String(b) => b.finish().into_series(),
+            Self::Binary(b) => b.finish().into_series(),
+            Self::List(b) => b.finish()?,
+            #[cfg(feature = "dtype-categorical")]
+            Self::Categorical(b) => b.finish()?,
+            #[cfg(feature = "dtype-array")]
+            Self::Array(b) => b.finish()?,
+            #[cfg(feature = "dtype-struct")]
+            Self::Struct(b) => b.finish()?,
+            Self::Inferred(b) => b.finish()?,
+            #[cfg(any(
+                feature = "dtype-date",
+                feature = "dtype-datetime",
+                feature = "dtype-duration",
+                feature = "dtype-time",
+                feature = "dtype-decimal",
+            ))]
+            Self::Logical(b, dtype) => {
+                let series = b.finish()?;
+                match dtype {
+                    #[cfg(feature = "dtype-date")]
+                    DataType::Date => series.into_date(),
+                    #[cfg(feature = "dtype-datetime")]
+                    DataType::Datetime(tu, tz) => series.into_datetime(tu, tz),
+                    #[cfg(feature = "dtype-duration")]
+                    DataType::Duration(tu) => series.into_duration(tu),
+                    #[cfg(feature = "dtype-time")]
+                    DataType::Time => series.into_time(),
+                    #[cfg(feature = "dtype-decimal")]
+                    DataType::Decimal(precision, scale) => series.into_decimal(precision, scale)?,
+                    dtype => return Err(super::Error::UnsupportedDataType(dtype)),
+                }
+            }
+        })
+    }
+}
+
+pub(super) struct ListBuilder {
+    values: Builder,
+    offsets: Offsets<i64>,
+    validity: MutableBitmap,
+}
+
+impl ListBuilder {
+    fn finish(self) -> Result<Series, super::Error> {
+        let values = self.values.finish()?.rechunk();
+        let dtype = DataType::List(Box::new(values.dtype().clone()));
+        let values = values.chunks()[0].clone();
+        let array = ListArray::<i64>::new(
+            ListArray::<i64>::default_datatype(values.dtype().clone()),
+            self.offsets.into(),
+            values,
+            self.validity.into(),
+        );
+        let series = ListChunked::with_chunk(PlSmallStr::EMPTY, array).into_series();
+        // the physical array loses logical types of the values
+        if series.dtype() == &dtype {
+            Ok(series)
+        } else {
+            Ok(series.cast(&dtype)?)
+        }
+    }
+}
+
+/// Collects the labels of a `Categorical` or `Enum` column and casts them once finished.
+#[cfg(feature = "dtype-categorical")]
+pub(super) struct CategoricalBuilder {
+    dtype: Option<DataType>,
+    name: Option<&'static str>,
+    variants: BTreeMap<u32, &'static str>,
+    labels: PlIndexSet<PlSmallStr>,
+    values: StringChunkedBuilder,
+}
+
+#[cfg(feature = "dtype-categorical")]
+impl CategoricalBuilder {
+    fn dtype(&self) -> Result<DataType, super::Error> {
+        match &self.dtype {
+            Some(dtype) => Ok(dtype.clone()),
+            // categories are ordered by variant index, like the enum declaration,
+            // followed by other labels in order of appearance
+            None => Ok(DataType::from_frozen_categories(FrozenCategories::new(
+                self.variants.values().copied().chain(
+                    self.labels
+                        .iter()
+                        .map(PlSmallStr::as_str)
+                        .filter(|v| !self.variants.values().any(|variant| variant == v)),
+                ),
+            )?)),
+        }
+    }
+
+    fn finish(self) -> Result<Series, super::Error> {
+        let dtype = self.dtype()?;
+        // labels outside the categories of an `Enum` fail instead of becoming null
+        Ok(self.values.finish().into_series().strict_cast(&dtype)?)
+    }
+}
+
+#[cfg(feature = "dtype-array")]
+pub(super) struct ArrayBuilder {
+    values: Builder,
+    width: usize,
+    validity: MutableBitmap,
+}
+
+#[cfg(feature = "dtype-array")]
+impl ArrayBuilder {
+    fn finish(self) -> Result<Series, super::Error> {
+        let values = self.values.finish()?.rechunk();
+        let dtype = DataType::Array(Box::new(values.dtype().clone()), self.width);
+        let values = values.chunks()[0].clone();
+        let array = FixedSizeListArray::new(
+            FixedSizeListArray::default_datatype(values.dtype().clone(), self.width),
+            self.validity.len(),
+            values,
+            self.validity.into(),
+        );
+        let series = ArrayChunked::with_chunk(PlSmallStr::EMPTY, array).into_series();
+        // the physical array loses logical types of the values
+        if series.dtype() == &dtype {
+            Ok(series)
+        } else {
+            Ok(series.cast(&dtype)?)
+        }
+    }
+}
+
+#[cfg(feature = "dtype-struct")]
+pub(super) struct StructBuilder {
+    fields: super::row::Columns,
+    validity: MutableBitmap,
+}
+
+#[cfg(feature = "dtype-struct")]
+impl StructBuilder {
+    fn finish(self) -> Result<Series, super::Error> {
+        let fields = self.fields.finish()?;
+        Ok(
+            StructChunked::from_columns(PlSmallStr::EMPTY, fields.height(), fields.columns())?
+                .with_outer_validity(self.validity.into())
+                .into_series(),
+        )
+    }
+}
+
+/// Appends a single value to a [`Builder`].
+pub(super) struct ValueSerializer<'a>(pub(super) &'a mut Builder);
+
+impl<'a> ser::Serializer for ValueSerializer<'a> {
+    type Ok = ();
+    type Error = super::Error;
+    type SerializeSeq = SeqSerializer<'a>;
+    type SerializeTuple = SeqSerializer<'a>;
+    type SerializeTupleStruct = SeqSerializer<'a>;
+    type SerializeTupleVariant = ser::Impossible<(), super::Error>;
+    #[cfg(feature = "dtype-struct")]
+    type SerializeMap = super::row::StructSerializer<'a>;
+    #[cfg(not(feature = "dtype-struct"))]
+    type SerializeMap = ser::Impossible<(), super::Error>;
+    #[cfg(feature = "dtype-struct")]
+    type SerializeStruct = super::row::StructSerializer<'a>;
+    #[cfg(not(feature = "dtype-struct"))]
+    type SerializeStruct = ser::Impossible<(), super::Error>;
+    type SerializeStructVariant = ser::Impossible<(), super::Error>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        self.0.push_bool(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        #[cfg(feature = "dtype-i8")]
+        let dtype = DataType::Int8;
+        #[cfg(not(feature = "dtype-i8"))]
+        let dtype = DataType::Int32;
+        self.0.push_num(v, dtype, "i8")
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        #[cfg(feature = "dtype-i16")]
+        let dtype = DataType::Int16;
+        #[cfg(not(feature = "dtype-i16"))]
+        let dtype = DataType::Int32;
+        self.0.push_num(v, dtype, "i16")
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        self.0.push_num(v, DataType::Int32, "i32")
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        self.0.push_num(v, DataType::Int64, "i64")
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        #[cfg(feature = "dtype-i128")]
+        let dtype = DataType::Int128;
+        #[cfg(not(feature = "dtype-i128"))]
+        let dtype = DataType::Int64;
+        self.0.push_num(v, dtype, "i128")
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        #[cfg(feature = "dtype-u8")]
+        let dtype = DataType::UInt8;
+        #[cfg(not(feature = "dtype-u8"))]
+        let dtype = DataType::UInt32;
+        self.0.push_num(v, dtype, "u8")
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        #[cfg(feature = "dtype-u16")]
+        let dtype = DataType::UInt16;
+        #[cfg(not(feature = "dtype-u16"))]
+        let dtype = DataType::UInt32;
+        self.0.push_num(v, dtype, "u16")
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.0.push_num(v, DataType::UInt32, "u32")
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        self.0.push_num(v, DataType::UInt64, "u64")
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        #[cfg(feature = "dtype-u128")]
+        let dtype = DataType::UInt128;
+        #[cfg(not(feature = "dtype-u128"))]
+        let dtype = DataType::UInt64;
+        self.0.push_num(v, dtype, "u128")
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        self.0.push_num(v, DataType::Float32, "f32")
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        self.0.push_num(v, DataType::Float64, "f64")
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        self.0.push_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.0.push_str(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        self.0.push_bytes(v)
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        self.0.push_null();
+        Ok(())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        self.serialize_none()
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_none()
+    }
+
+    #[cfg(feature = "dtype-categorical")]
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.0.push_variant(name, variant_index, variant)
+    }
+
+    #[cfg(not(feature = "dtype-categorical"))]
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(variant)
+    }
+
+    #[allow(unused_variables)]
+    fn serialize_newtype_struct<T>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        #[cfg(feature = "dtype-categorical")]
+        if name == crate::value::CATEGORICAL || name == crate::value::ENUM {
+            let dtype = (name == crate::value::CATEGORICAL)
+                .then(|| DataType::from_categories(Categories::global()));
+            return self.0.push_label(&super::logical::label(value)?, dtype);
+        }
+        #[cfg(any(
+            feature = "dtype-date",
+            feature = "dtype-datetime",
+            feature = "dtype-duration",
+            feature = "dtype-time",
+            feature = "dtype-decimal",
+        ))]
+        if let Some((dtype, physical)) = super::logical::split(name, value)? {
+            return self.0.push_logical(&dtype, physical);
+        }
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        self.0.seq(DataType::List(Box::new(DataType::Null)), "seq")
+    }
+
+    // fixed-size arrays such as `[f32; N]` are serialized as tuples
+    #[cfg(feature = "dtype-array")]
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.0
+            .seq(DataType::Array(Box::new(DataType::Null), len), "tuple")
+    }
+
+    #[cfg(not(feature = "dtype-array"))]
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.0
+            .seq(DataType::List(Box::new(DataType::Null)), "tuple")
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.0
+            .seq(DataType::List(Box::new(DataType::Null)), "tuple struct")
+    }
+
+    #[cfg(feature = "dtype-struct")]
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(super::row::StructSerializer::new(self.0.fields("map")?))
+    }
+
+    #[cfg(feature = "dtype-struct")]
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(super::row::StructSerializer::new(self.0.fields("struct")?))
+    }
+
+    serialize_unsupported! {
+        serialize_newtype_variant
+        serialize_tuple_variant
+        serialize_struct_variant
+    }
+
+    #[cfg(not(feature = "dtype-struct"))]
+    serialize_unsupported! {
+        serialize_map
+        serialize_struct
+    }
+}
+
+pub(super) enum SeqSerializer<'a> {
+    List(&'a mut ListBuilder, usize),
+    #[cfg(feature = "dtype-array")]
+    Array(&'a mut ArrayBuilder),
+}
+
+impl SeqSerializer<'_> {
+    fn push<T>(&mut self, value: &T) -> Result<(), super::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        match self {
+            Self::List(b, len) => {
+                value.serialize(ValueSerializer(&mut b.values))?;
+                *len += 1;
+            }
+            #[cfg(feature = "dtype-array")]
+            Self::Array(b) => value.serialize(ValueSerializer(&mut b.values))?,
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), super::Error> {
+        match self {
+            Self::List(b, len) => {
+                b.offsets.try_push(len)?;
+                b.validity.push(true);
+            }
+            #[cfg(feature = "dtype-array")]
+            Self::Array(b) => b.validity.push(true),
+        }
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer<'_> {
+    type Ok = ();
+    type Error = super::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer<'_> {
+    type Ok = ();
+    type Error = super::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer<'_> {
+    type Ok = ();
+    type Error = super::Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
@@ -0,0 +1,21 @@
+use polars_core::datatypes::{DataType, FrozenCategories};
+use serde::de;
+
+/// Returns an `Enum` data type whose categories are the variants of `T` in declaration order.
+///
+/// Pass it in the schema of a [`DataFrameSerializer`](super::DataFrameSerializer) so that
+/// variants missing from the serialized rows are still part of the categories.
+pub fn enum_dtype<'de, T>() -> Result<DataType, super::Error>
+where
+    T: de::Deserialize<'de>,
+{
+    match crate::trace::shape::<T>() {
+        Some(crate::trace::Shape::Enum(variants)) => Ok(DataType::from_frozen_categories(
+            FrozenCategories::new(variants.iter().copied())?,
+        )),
+        _ => Err(<super::Error as serde::ser::Error>::custom(format_args!(
+            "{} is not an enum",
+            std::any::type_name::<T>(),
+        ))),
+    }
+}
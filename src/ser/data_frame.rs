@@ -0,0 +1,612 @@
+use crate::de::CategoricalRepr;
+use crate::trace::Variants;
+use polars_core::datatypes::AnyValue;
+use polars_core::frame::DataFrame;
+use polars_core::frame::column::Column;
+use polars_core::prelude::PlSmallStr;
+use polars_core::schema::Schema;
+use polars_core::series::Series;
+use serde::{de, ser};
+
+/// Serializes a sequence of rows, such as a `Vec` of structs, into a frame.
+///
+/// With `dtype-categorical` but without a schema, unit enum variants become an `Enum` of all the
+/// variants of their enum, traced from the row type `R`.
+pub fn to_data_frame<'de, R>(rows: &[R]) -> Result<DataFrame, super::Error>
+where
+    R: ser::Serialize + de::Deserialize<'de>,
+{
+    ser::Serialize::serialize(rows, Serializer::rows().with_row_type::<R>())
+}
+
+/// Serializes a frame either as a sequence of rows or as a map of column names to values.
+pub struct SerializeDataFrame<'a>(&'a DataFrame, Order, CategoricalRepr);
+
+impl<'a> SerializeDataFrame<'a> {
+    pub fn columns(value: &'a DataFrame) -> Self {
+        Self(value, Order::Column, CategoricalRepr::default())
+    }
+
+    pub fn rows(value: &'a DataFrame) -> Self {
+        Self(value, Order::Row, CategoricalRepr::default())
+    }
+
+    pub fn with_categorical(self, categorical: CategoricalRepr) -> Self {
+        Self(self.0, self.1, categorical)
+    }
+}
+
+impl ser::Serialize for SerializeDataFrame<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match self.1 {
+            Order::Column => serializer.collect_map(
+                self.0
+                    .columns()
+                    .iter()
+                    .map(|column| (column.name().as_str(), SerializeColumn(column, self.2))),
+            ),
+            Order::Row => {
+                let names = self
+                    .0
+                    .columns()
+                    .iter()
+                    .map(|column| column.name().as_str())
+                    .collect::<Vec<_>>();
+                let mut columns = self
+                    .0
+                    .columns()
+                    .iter()
+                    .map(|column| crate::de::cells(column, Series::iter))
+                    .collect::<Vec<_>>();
+                // one buffer is reused for the values of every row
+                let mut values = Vec::with_capacity(columns.len());
+                let mut seq = serializer.serialize_seq(Some(self.0.height()))?;
+                for _ in 0..self.0.height() {
+                    values.clear();
+                    values.extend(
+                        columns
+                            .iter_mut()
+                            .map(|column| column.next().unwrap_or_default()),
+                    );
+                    ser::SerializeSeq::serialize_element(
+                        &mut seq,
+                        &SerializeRow(&names, &values, self.2),
+                    )?;
+                }
+                ser::SerializeSeq::end(seq)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Order {
+    Column,
+    Row,
+}
+
+struct SerializeColumn<'a>(&'a Column, CategoricalRepr);
+
+impl ser::Serialize for SerializeColumn<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        super::any_value::serialize_values(
+            serializer,
+            self.0.len(),
+            crate::de::cells(self.0, Series::iter),
+            self.1,
+        )
+    }
+}
+
+struct SerializeRow<'a>(&'a [&'a str], &'a [AnyValue<'a>], CategoricalRepr);
+
+impl ser::Serialize for SerializeRow<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.collect_map(self.0.iter().zip(self.1).map(|(name, value)| {
+            (
+                name,
+                super::SerializeAnyValue::new(value).with_categorical(self.2),
+            )
+        }))
+    }
+}
+
+pub struct Serializer(Option<Schema>, Option<PlSmallStr>, Option<Variants>);
+
+impl Serializer {
+    pub fn rows() -> Self {
+        Self(None, None, None)
+    }
+
+    /// Uses the data types in `schema` for the named columns instead of inferring them from the first values.
+    pub fn with_schema(self, schema: Schema) -> Self {
+        Self(Some(schema), self.1, self.2)
+    }
+
+    /// Flattens nested structs into columns named like `address.city` instead of `Struct` columns.
+    pub fn with_separator(self, separator: &str) -> Self {
+        Self(self.0, Some(separator.into()), self.2)
+    }
+
+    /// Traces the enums of the row type `R`, so that unit variants without a schema become an
+    /// `Enum` of all the variants of their enum rather than only those that appear.
+    pub fn with_row_type<'de, R>(self) -> Self
+    where
+        R: de::Deserialize<'de>,
+    {
+        Self(self.0, self.1, Some(crate::trace::variants::<R>()))
+    }
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = DataFrame;
+    type Error = super::Error;
+    type SerializeSeq = RowsSerializer;
+    type SerializeTuple = RowsSerializer;
+    type SerializeTupleStruct = ser::Impossible<DataFrame, super::Error>;
+    type SerializeTupleVariant = ser::Impossible<DataFrame, super::Error>;
+    type SerializeMap = ser::Impossible<DataFrame, super::Error>;
+    type SerializeStruct = ser::Impossible<DataFrame, super::Error>;
+    type SerializeStructVariant = ser::Impossible<DataFrame, super::Error>;
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(RowsSerializer(
+            super::row::Columns::new(self.0.as_ref(), len.unwrap_or(0))?
+                .with_separator(self.1)
+                .with_variants(self.2),
+        ))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    serialize_unsupported! {
+        serialize_bool
+        serialize_i8
+        serialize_i16
+        serialize_i32
+        serialize_i64
+        serialize_i128
+        serialize_u8
+        serialize_u16
+        serialize_u32
+        serialize_u64
+        serialize_u128
+        serialize_f32
+        serialize_f64
+        serialize_char
+        serialize_str
+        serialize_bytes
+        serialize_none
+        serialize_some
+        serialize_unit
+        serialize_unit_struct
+        serialize_unit_variant
+        serialize_newtype_variant
+        serialize_tuple_struct
+        serialize_tuple_variant
+        serialize_map
+        serialize_struct
+        serialize_struct_variant
+    }
+}
+
+pub struct RowsSerializer(super::row::Columns);
+
+impl ser::SerializeSeq for RowsSerializer {
+    type Ok = DataFrame;
+    type Error = super::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(super::row::Serializer(&mut self.0))
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.0.finish()
+    }
+}
+
+impl ser::SerializeTuple for RowsSerializer {
+    type Ok = DataFrame;
+    type Error = super::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use polars_core::datatypes::DataType;
+    #[cfg(all(feature = "dtype-array", feature = "dtype-struct", feature = "rows"))]
+    use polars_core::datatypes::Field;
+    use polars_core::frame::DataFrame;
+    use polars_core::frame::column::Column;
+    use polars_core::schema::Schema;
+    use serde::Serialize;
+
+    #[test]
+    fn test_serialize_data_frame() {
+        let s1 = Column::new("Ocean".into(), ["Atlantic", "Indian"]);
+        let s2 = Column::new("Area (km²)".into(), [None, Some(70_560_000_u64)]);
+        let df = DataFrame::new_infer_height(vec![s1, s2]).unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&super::SerializeDataFrame::rows(&df)).unwrap(),
+            r#"[{"Ocean":"Atlantic","Area (km²)":null},{"Ocean":"Indian","Area (km²)":70560000}]"#,
+        );
+        assert_eq!(
+            serde_json::to_string(&super::SerializeDataFrame::columns(&df)).unwrap(),
+            r#"{"Ocean":["Atlantic","Indian"],"Area (km²)":[null,70560000]}"#,
+        );
+    }
+
+    #[cfg(feature = "dtype-categorical")]
+    #[test]
+    fn test_serialize_data_frame_categorical() {
+        use crate::de::CategoricalRepr;
+        use serde::Deserialize;
+
+        #[derive(Serialize, Deserialize)]
+        enum Color {
+            Red,
+            Green,
+            Blue,
+        }
+
+        #[derive(Serialize, Deserialize)]
+        struct Row {
+            color: Color,
+        }
+
+        let df =
+            super::to_data_frame(&[Row { color: Color::Blue }, Row { color: Color::Red }]).unwrap();
+        assert_eq!(
+            serde_json::to_string(&super::SerializeDataFrame::columns(&df)).unwrap(),
+            r#"{"color":["Blue","Red"]}"#,
+        );
+        // every representation matches what the deserializer presents
+        for categorical in [CategoricalRepr::Code, CategoricalRepr::Both] {
+            assert_eq!(
+                serde_json::to_value(
+                    super::SerializeDataFrame::columns(&df).with_categorical(categorical),
+                )
+                .unwrap(),
+                serde_json::Value::deserialize(
+                    crate::de::DataFrameDeserializer::columns(&df).with_categorical(categorical),
+                )
+                .unwrap(),
+            );
+        }
+        assert_eq!(
+            serde_json::to_string(
+                &super::SerializeDataFrame::columns(&df).with_categorical(CategoricalRepr::Code),
+            )
+            .unwrap(),
+            r#"{"color":[2,0]}"#,
+        );
+    }
+
+    #[test]
+    fn test_rows() {
+        #[derive(Serialize)]
+        struct Row<'a> {
+            #[serde(rename = "Ocean")]
+            ocean: &'a str,
+            #[serde(rename = "Area (km²)")]
+            area: Option<u64>,
+            #[serde(rename = "Seas")]
+            seas: Vec<&'a str>,
+        }
+
+        let rows = [
+            Row {
+                ocean: "Atlantic",
+                area: None,
+                seas: vec!["Caribbean", "Sargasso"],
+            },
+            Row {
+                ocean: "Indian",
+                area: Some(70_560_000),
+                seas: vec![],
+            },
+        ];
+
+        let df = rows.serialize(super::Serializer::rows()).unwrap();
+        let s1 = Column::new("Ocean".into(), ["Atlantic", "Indian"]);
+        let s2 = Column::new("Area (km²)".into(), [None, Some(70_560_000_u64)]);
+        let s3 = Column::new(
+            "Seas".into(),
+            [
+                Column::new("".into(), ["Caribbean", "Sargasso"]).take_materialized_series(),
+                Column::new_empty("".into(), &DataType::String).take_materialized_series(),
+            ],
+        );
+        assert_eq!(df, DataFrame::new_infer_height(vec![s1, s2, s3]).unwrap());
+    }
+
+    #[test]
+    fn test_rows_with_schema() {
+        #[derive(Serialize)]
+        struct Row {
+            a: Option<i32>,
+            b: f32,
+        }
+
+        let rows = [
+            Row { a: None, b: 1.5 },
+            Row {
+                a: Some(42),
+                b: 2.5,
+            },
+        ];
+
+        let schema = Schema::from_iter([
+            ("b".into(), DataType::Float64),
+            ("a".into(), DataType::Int64),
+        ]);
+        let df = rows
+            .serialize(super::Serializer::rows().with_schema(schema))
+            .unwrap();
+        let s1 = Column::new("b".into(), [1.5_f64, 2.5]);
+        let s2 = Column::new("a".into(), [None, Some(42_i64)]);
+        assert_eq!(df, DataFrame::new_infer_height(vec![s1, s2]).unwrap());
+    }
+
+    #[test]
+    fn test_rows_widening() {
+        let df = super::to_data_frame(&[
+            serde_json::json!({ "a": 1, "b": 1 }),
+            serde_json::json!({ "a": -1, "b": 1.5 }),
+        ])
+        .unwrap();
+        let s1 = Column::new("a".into(), [1_i64, -1]);
+        let s2 = Column::new("b".into(), [1.0_f64, 1.5]);
+        assert_eq!(df, DataFrame::new_infer_height(vec![s1, s2]).unwrap());
+
+        // integers that fit no `Int64` are kept exactly or rejected, never rounded into floats
+        let rows = [
+            serde_json::json!({ "c": u64::MAX, "d": -1 }),
+            serde_json::json!({ "c": -1, "d": u64::MAX }),
+        ];
+        #[cfg(feature = "dtype-i128")]
+        {
+            let s3 = Column::new("c".into(), [i128::from(u64::MAX), -1]);
+            let s4 = Column::new("d".into(), [-1, i128::from(u64::MAX)]);
+            assert_eq!(
+                super::to_data_frame(&rows).unwrap(),
+                DataFrame::new_infer_height(vec![s3, s4]).unwrap(),
+            );
+        }
+        #[cfg(not(feature = "dtype-i128"))]
+        assert!(matches!(
+            super::to_data_frame(&rows),
+            Err(crate::ser::Error::OutOfRange(DataType::UInt64)),
+        ));
+
+        let schema = Schema::from_iter([("a".into(), DataType::UInt64)]);
+        assert!(matches!(
+            [
+                serde_json::json!({ "a": 1 }),
+                serde_json::json!({ "a": -1 })
+            ]
+            .serialize(super::Serializer::rows().with_schema(schema)),
+            Err(crate::ser::Error::OutOfRange(DataType::UInt64)),
+        ));
+    }
+
+    #[cfg(all(feature = "dtype-array", feature = "dtype-struct", feature = "rows"))]
+    #[test]
+    fn test_rows_nested() {
+        use serde::Deserialize;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Address {
+            city: String,
+            zip: Option<u32>,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Row {
+            address: Option<Address>,
+            tags: Vec<Vec<String>>,
+            embedding: [f32; 2],
+        }
+
+        let rows = vec![
+            Row {
+                address: None,
+                tags: vec![],
+                embedding: [0.5, 1.5],
+            },
+            Row {
+                address: Some(Address {
+                    city: "Kyoto".to_owned(),
+                    zip: Some(6_008_501),
+                }),
+                tags: vec![vec!["a".to_owned()], vec![]],
+                embedding: [2.5, 3.5],
+            },
+        ];
+
+        let df = rows.serialize(super::Serializer::rows()).unwrap();
+        assert_eq!(
+            df.dtypes(),
+            [
+                DataType::Struct(vec![
+                    Field::new("city".into(), DataType::String),
+                    Field::new("zip".into(), DataType::UInt32),
+                ]),
+                DataType::List(Box::new(DataType::List(Box::new(DataType::String)))),
+                DataType::Array(Box::new(DataType::Float32), 2),
+            ],
+        );
+        assert_eq!(
+            Vec::<Row>::deserialize(crate::de::DataFrameDeserializer::rows(&df)).unwrap(),
+            rows,
+        );
+    }
+
+    #[cfg(all(feature = "dtype-categorical", feature = "rows"))]
+    #[test]
+    fn test_rows_enum() {
+        use polars_core::datatypes::Categories;
+        use serde::Deserialize;
+        use std::collections::HashMap;
+
+        #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+        enum Color {
+            Red,
+            Green,
+            Blue,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Row {
+            color: Option<Color>,
+        }
+
+        let rows = vec![
+            Row { color: None },
+            Row {
+                color: Some(Color::Blue),
+            },
+            Row {
+                color: Some(Color::Red),
+            },
+        ];
+
+        let df = super::to_data_frame(&rows).unwrap();
+        let DataType::Enum(categories, _) = df.column("color").unwrap().dtype() else {
+            panic!();
+        };
+        assert_eq!(
+            categories.categories().values_iter().collect::<Vec<_>>(),
+            ["Red", "Green", "Blue"],
+        );
+        assert_eq!(
+            Vec::<Row>::deserialize(crate::de::DataFrameDeserializer::rows(&df)).unwrap(),
+            rows,
+        );
+
+        let schema =
+            Schema::from_iter([("color".into(), crate::ser::enum_dtype::<Color>().unwrap())]);
+        let df = rows
+            .serialize(super::Serializer::rows().with_schema(schema))
+            .unwrap();
+        let DataType::Enum(categories, _) = df.column("color").unwrap().dtype() else {
+            panic!();
+        };
+        assert_eq!(
+            categories.categories().values_iter().collect::<Vec<_>>(),
+            ["Red", "Green", "Blue"],
+        );
+
+        let schema = Schema::from_iter([(
+            "color".into(),
+            DataType::from_categories(Categories::global()),
+        )]);
+        let df = rows
+            .serialize(super::Serializer::rows().with_schema(schema))
+            .unwrap();
+        assert!(df.column("color").unwrap().dtype().is_categorical());
+        assert_eq!(
+            Vec::<Row>::deserialize(crate::de::DataFrameDeserializer::rows(&df)).unwrap(),
+            rows,
+        );
+        #[derive(Serialize)]
+        enum Other {
+            Purple,
+        }
+
+        let schema =
+            Schema::from_iter([("color".into(), crate::ser::enum_dtype::<Color>().unwrap())]);
+        let err = [HashMap::from([("color", Other::Purple)])]
+            .serialize(super::Serializer::rows().with_schema(schema.clone()))
+            .unwrap_err();
+        assert!(matches!(err, crate::ser::Error::Polars(_)));
+        assert!(
+            [HashMap::from([("color", "Purple")])]
+                .serialize(super::Serializer::rows().with_schema(schema))
+                .is_err()
+        );
+    }
+
+    #[cfg(feature = "rows")]
+    #[test]
+    fn test_rows_with_separator() {
+        use serde::Deserialize;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Address {
+            city: String,
+            zip: u32,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Row {
+            name: String,
+            address: Option<Address>,
+        }
+
+        let rows = vec![
+            Row {
+                name: "Alice".to_owned(),
+                address: None,
+            },
+            Row {
+                name: "Bob".to_owned(),
+                address: Some(Address {
+                    city: "Kyoto".to_owned(),
+                    zip: 6_008_501,
+                }),
+            },
+        ];
+
+        let df = rows
+            .serialize(super::Serializer::rows().with_separator("."))
+            .unwrap();
+        let s1 = Column::new("name".into(), ["Alice", "Bob"]);
+        let s2 = Column::new("address.city".into(), [None, Some("Kyoto")]);
+        let s3 = Column::new("address.zip".into(), [None, Some(6_008_501_u32)]);
+        assert_eq!(df, DataFrame::new_infer_height(vec![s1, s2, s3]).unwrap());
+        assert_eq!(
+            Vec::<Row>::deserialize(
+                crate::de::DataFrameDeserializer::rows(&df).with_separator(".")
+            )
+            .unwrap(),
+            rows,
+        );
+    }
+}
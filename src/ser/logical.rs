@@ -0,0 +1,171 @@
+use crate::Value;
+#[cfg(any(
+    feature = "dtype-date",
+    feature = "dtype-datetime",
+    feature = "dtype-duration",
+    feature = "dtype-time",
+    feature = "dtype-decimal",
+))]
+use polars_core::datatypes::DataType;
+#[cfg(feature = "dtype-datetime")]
+use polars_core::datatypes::TimeZone;
+use serde::{Deserialize, ser};
+
+#[cfg(feature = "dtype-categorical")]
+pub(super) fn label<T>(value: &T) -> Result<String, super::Error>
+where
+    T: ?Sized + ser::Serialize,
+{
+    parts(value)
+}
+
+/// Reads a logical [`Value`] serialized as a newtype struct into its data type and physical value.
+#[cfg(any(
+    feature = "dtype-date",
+    feature = "dtype-datetime",
+    feature = "dtype-duration",
+    feature = "dtype-time",
+    feature = "dtype-decimal",
+))]
+pub(super) fn split<T>(
+    name: &'static str,
+    value: &T,
+) -> Result<Option<(DataType, Value)>, super::Error>
+where
+    T: ?Sized + ser::Serialize,
+{
+    #[cfg(any(feature = "dtype-datetime", feature = "dtype-duration"))]
+    fn unit(unit: &str) -> Result<polars_core::datatypes::TimeUnit, super::Error> {
+        crate::value::time_unit(unit).ok_or_else(|| {
+            <super::Error as ser::Error>::custom(format_args!("unknown time unit {unit}"))
+        })
+    }
+
+    Ok(Some(match name {
+        #[cfg(feature = "dtype-date")]
+        crate::value::DATE => (DataType::Date, Value::Int32(parts(value)?)),
+        #[cfg(feature = "dtype-datetime")]
+        crate::value::DATETIME => {
+            let (v, tu, tz): (_, String, Option<String>) = parts(value)?;
+            (
+                DataType::Datetime(unit(&tu)?, TimeZone::opt_try_new(tz)?),
+                Value::Int64(v),
+            )
+        }
+        #[cfg(feature = "dtype-duration")]
+        crate::value::DURATION => {
+            let (v, tu): (_, String) = parts(value)?;
+            (DataType::Duration(unit(&tu)?), Value::Int64(v))
+        }
+        #[cfg(feature = "dtype-time")]
+        crate::value::TIME => (DataType::Time, Value::Int64(parts(value)?)),
+        #[cfg(feature = "dtype-decimal")]
+        crate::value::DECIMAL => {
+            let (v, precision, scale) = parts(value)?;
+            (DataType::Decimal(precision, scale), Value::Int128(v))
+        }
+        _ => return Ok(None),
+    }))
+}
+
+fn parts<'de, T, P>(value: &T) -> Result<P, super::Error>
+where
+    T: ?Sized + ser::Serialize,
+    P: Deserialize<'de>,
+{
+    P::deserialize(value.serialize(Capture)?).map_err(<super::Error as ser::Error>::custom)
+}
+
+// the payloads are plain numbers, strings and tuples of them
+struct Capture;
+
+impl ser::Serializer for Capture {
+    type Ok = Value;
+    type Error = super::Error;
+    type SerializeSeq = ser::Impossible<Value, super::Error>;
+    type SerializeTuple = CaptureTuple;
+    type SerializeTupleStruct = ser::Impossible<Value, super::Error>;
+    type SerializeTupleVariant = ser::Impossible<Value, super::Error>;
+    type SerializeMap = ser::Impossible<Value, super::Error>;
+    type SerializeStruct = ser::Impossible<Value, super::Error>;
+    type SerializeStructVariant = ser::Impossible<Value, super::Error>;
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Int32(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Int64(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Int128(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::UInt64(v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::String(v.to_owned()))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(CaptureTuple(Vec::with_capacity(len)))
+    }
+
+    serialize_unsupported! {
+        serialize_bool
+        serialize_i8
+        serialize_i16
+        serialize_u8
+        serialize_u16
+        serialize_u32
+        serialize_u128
+        serialize_f32
+        serialize_f64
+        serialize_char
+        serialize_bytes
+        serialize_unit
+        serialize_unit_struct
+        serialize_unit_variant
+        serialize_newtype_struct
+        serialize_newtype_variant
+        serialize_seq
+        serialize_tuple_struct
+        serialize_tuple_variant
+        serialize_map
+        serialize_struct
+        serialize_struct_variant
+    }
+}
+
+struct CaptureTuple(Vec<Value>);
+
+impl ser::SerializeTuple for CaptureTuple {
+    type Ok = Value;
+    type Error = super::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.0.push(value.serialize(Capture)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::List(self.0))
+    }
+}
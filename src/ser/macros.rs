@@ -0,0 +1,199 @@
+macro_rules! serialize_unsupported {
+    ($($method:ident)*) => {
+        $(serialize_unsupported!(@ $method);)*
+    };
+    (@ serialize_bool) => {
+        fn serialize_bool(self, _: bool) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("bool"))
+        }
+    };
+    (@ serialize_i8) => {
+        fn serialize_i8(self, _: i8) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("i8"))
+        }
+    };
+    (@ serialize_i16) => {
+        fn serialize_i16(self, _: i16) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("i16"))
+        }
+    };
+    (@ serialize_i32) => {
+        fn serialize_i32(self, _: i32) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("i32"))
+        }
+    };
+    (@ serialize_i64) => {
+        fn serialize_i64(self, _: i64) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("i64"))
+        }
+    };
+    (@ serialize_i128) => {
+        fn serialize_i128(self, _: i128) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("i128"))
+        }
+    };
+    (@ serialize_u8) => {
+        fn serialize_u8(self, _: u8) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("u8"))
+        }
+    };
+    (@ serialize_u16) => {
+        fn serialize_u16(self, _: u16) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("u16"))
+        }
+    };
+    (@ serialize_u32) => {
+        fn serialize_u32(self, _: u32) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("u32"))
+        }
+    };
+    (@ serialize_u64) => {
+        fn serialize_u64(self, _: u64) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("u64"))
+        }
+    };
+    (@ serialize_u128) => {
+        fn serialize_u128(self, _: u128) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("u128"))
+        }
+    };
+    (@ serialize_f32) => {
+        fn serialize_f32(self, _: f32) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("f32"))
+        }
+    };
+    (@ serialize_f64) => {
+        fn serialize_f64(self, _: f64) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("f64"))
+        }
+    };
+    (@ serialize_char) => {
+        fn serialize_char(self, _: char) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("char"))
+        }
+    };
+    (@ serialize_str) => {
+        fn serialize_str(self, _: &str) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("str"))
+        }
+    };
+    (@ serialize_bytes) => {
+        fn serialize_bytes(self, _: &[u8]) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("bytes"))
+        }
+    };
+    (@ serialize_none) => {
+        fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("none"))
+        }
+    };
+    (@ serialize_some) => {
+        fn serialize_some<T>(self, _: &T) -> Result<Self::Ok, Self::Error>
+        where
+            T: ?Sized + ser::Serialize,
+        {
+            Err(super::Error::Unsupported("some"))
+        }
+    };
+    (@ serialize_unit) => {
+        fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("unit"))
+        }
+    };
+    (@ serialize_unit_struct) => {
+        fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("unit struct"))
+        }
+    };
+    (@ serialize_unit_variant) => {
+        fn serialize_unit_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+        ) -> Result<Self::Ok, Self::Error> {
+            Err(super::Error::Unsupported("unit variant"))
+        }
+    };
+    (@ serialize_newtype_struct) => {
+        fn serialize_newtype_struct<T>(
+            self,
+            _: &'static str,
+            _: &T,
+        ) -> Result<Self::Ok, Self::Error>
+        where
+            T: ?Sized + ser::Serialize,
+        {
+            Err(super::Error::Unsupported("newtype struct"))
+        }
+    };
+    (@ serialize_newtype_variant) => {
+        fn serialize_newtype_variant<T>(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: &T,
+        ) -> Result<Self::Ok, Self::Error>
+        where
+            T: ?Sized + ser::Serialize,
+        {
+            Err(super::Error::Unsupported("newtype variant"))
+        }
+    };
+    (@ serialize_seq) => {
+        fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+            Err(super::Error::Unsupported("seq"))
+        }
+    };
+    (@ serialize_tuple) => {
+        fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+            Err(super::Error::Unsupported("tuple"))
+        }
+    };
+    (@ serialize_tuple_struct) => {
+        fn serialize_tuple_struct(
+            self,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+            Err(super::Error::Unsupported("tuple struct"))
+        }
+    };
+    (@ serialize_tuple_variant) => {
+        fn serialize_tuple_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+            Err(super::Error::Unsupported("tuple variant"))
+        }
+    };
+    (@ serialize_map) => {
+        fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+            Err(super::Error::Unsupported("map"))
+        }
+    };
+    (@ serialize_struct) => {
+        fn serialize_struct(
+            self,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStruct, Self::Error> {
+            Err(super::Error::Unsupported("struct"))
+        }
+    };
+    (@ serialize_struct_variant) => {
+        fn serialize_struct_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStructVariant, Self::Error> {
+            Err(super::Error::Unsupported("struct variant"))
+        }
+    };
+}
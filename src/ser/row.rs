@@ -0,0 +1,559 @@
+use super::builder::{Builder, ValueSerializer};
+use crate::trace::Variants;
+#[cfg(feature = "dtype-struct")]
+use polars_core::datatypes::Field;
+use polars_core::frame::DataFrame;
+use polars_core::frame::column::Column as PolarsColumn;
+use polars_core::prelude::PlSmallStr;
+use polars_core::schema::Schema;
+use serde::ser;
+
+/// The columns of a frame under construction, filled one row at a time.
+pub(super) struct Columns {
+    columns: Vec<Column>,
+    height: usize,
+    cursor: usize,
+    separator: Option<PlSmallStr>,
+    #[cfg_attr(not(feature = "dtype-categorical"), allow(dead_code))]
+    variants: Option<Variants>,
+}
+
+struct Column {
+    name: PlSmallStr,
+    builder: Builder,
+    len: usize,
+}
+
+impl Columns {
+    pub(super) fn new(schema: Option<&Schema>, capacity: usize) -> Result<Self, super::Error> {
+        let columns = schema
+            .into_iter()
+            .flat_map(|schema| schema.iter())
+            .map(|(name, dtype)| {
+                Ok(Column {
+                    name: name.clone(),
+                    builder: Builder::new(dtype, capacity)?,
+                    len: 0,
+                })
+            })
+            .collect::<Result<_, super::Error>>()?;
+        Ok(Self {
+            columns,
+            height: 0,
+            cursor: 0,
+            separator: None,
+            variants: None,
+        })
+    }
+
+    /// Flattens nested structs into columns named like `address.city` instead of `Struct` columns.
+    pub(super) fn with_separator(self, separator: Option<PlSmallStr>) -> Self {
+        Self { separator, ..self }
+    }
+
+    /// Completes the `Enum` columns of unit variants with the traced `variants` once finished.
+    pub(super) fn with_variants(self, variants: Option<Variants>) -> Self {
+        Self { variants, ..self }
+    }
+
+    fn column(&mut self, name: &str) -> &mut Column {
+        // fields usually come in the same order for every row
+        let index = if self
+            .columns
+            .get(self.cursor)
+            .is_some_and(|column| column.name == name)
+        {
+            self.cursor
+        } else if let Some(index) = self.columns.iter().position(|column| column.name == name) {
+            index
+        } else {
+            // a column first seen after some rows is null for those rows
+            self.columns.push(Column {
+                name: name.into(),
+                builder: Builder::Null(self.height),
+                len: self.height,
+            });
+            self.columns.len() - 1
+        };
+        self.cursor = index + 1;
+        &mut self.columns[index]
+    }
+
+    fn builder(&mut self, name: &str) -> &mut Builder {
+        let column = self.column(name);
+        column.len += 1;
+        &mut column.builder
+    }
+
+    fn push<T>(&mut self, name: &str, value: &T) -> Result<(), super::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        if self.separator.is_some() {
+            value.serialize(FlattenSerializer {
+                columns: self,
+                name: name.into(),
+            })
+        } else {
+            value.serialize(ValueSerializer(self.builder(name)))
+        }
+    }
+
+    /// Pads the columns missing from the current row with nulls.
+    pub(super) fn end_row(&mut self) {
+        self.height += 1;
+        self.cursor = 0;
+        for column in &mut self.columns {
+            if column.len < self.height {
+                column.builder.push_null();
+                column.len += 1;
+            }
+        }
+    }
+
+    #[cfg(feature = "dtype-categorical")]
+    pub(super) fn add_variants(&mut self, variants: &crate::trace::Variants) {
+        for column in &mut self.columns {
+            column.builder.add_variants(variants);
+        }
+    }
+
+    #[cfg(feature = "dtype-struct")]
+    pub(super) fn fields(&self) -> Vec<Field> {
+        self.columns
+            .iter()
+            .map(|column| Field::new(column.name.clone(), column.builder.dtype()))
+            .collect()
+    }
+
+    pub(super) fn finish(mut self) -> Result<DataFrame, super::Error> {
+        #[cfg(feature = "dtype-categorical")]
+        if let Some(variants) = self.variants.take() {
+            self.add_variants(&variants);
+        }
+        if let Some(separator) = &self.separator {
+            // drop the null placeholders written for missing structs whose fields were seen later
+            let placeholders = self
+                .columns
+                .iter()
+                .filter(|column| matches!(column.builder, Builder::Null(_)))
+                .filter(|column| {
+                    let prefix = format!("{}{separator}", column.name);
+                    self.columns
+                        .iter()
+                        .any(|other| other.name.starts_with(&prefix))
+                })
+                .map(|column| column.name.clone())
+                .collect::<Vec<_>>();
+            self.columns
+                .retain(|column| !placeholders.contains(&column.name));
+        }
+        let columns = self
+            .columns
+            .into_iter()
+            .map(|column| {
+                Ok(PolarsColumn::from(
+                    column.builder.finish()?.with_name(column.name),
+                ))
+            })
+            .collect::<Result<_, super::Error>>()?;
+        Ok(DataFrame::new(self.height, columns)?)
+    }
+}
+
+/// Appends a struct or a map as a row of [`Columns`].
+pub(super) struct Serializer<'a>(pub(super) &'a mut Columns);
+
+impl<'a> ser::Serializer for Serializer<'a> {
+    type Ok = ();
+    type Error = super::Error;
+    type SerializeSeq = ser::Impossible<(), super::Error>;
+    type SerializeTuple = ser::Impossible<(), super::Error>;
+    type SerializeTupleStruct = ser::Impossible<(), super::Error>;
+    type SerializeTupleVariant = ser::Impossible<(), super::Error>;
+    type SerializeMap = StructSerializer<'a>;
+    type SerializeStruct = StructSerializer<'a>;
+    type SerializeStructVariant = ser::Impossible<(), super::Error>;
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(StructSerializer::new(self.0))
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.serialize_map(None)
+    }
+
+    serialize_unsupported! {
+        serialize_bool
+        serialize_i8
+        serialize_i16
+        serialize_i32
+        serialize_i64
+        serialize_i128
+        serialize_u8
+        serialize_u16
+        serialize_u32
+        serialize_u64
+        serialize_u128
+        serialize_f32
+        serialize_f64
+        serialize_char
+        serialize_str
+        serialize_bytes
+        serialize_none
+        serialize_unit
+        serialize_unit_struct
+        serialize_unit_variant
+        serialize_newtype_variant
+        serialize_seq
+        serialize_tuple
+        serialize_tuple_struct
+        serialize_tuple_variant
+        serialize_struct_variant
+    }
+}
+
+pub(super) struct StructSerializer<'a> {
+    columns: &'a mut Columns,
+    prefix: Option<PlSmallStr>,
+    key: Option<PlSmallStr>,
+}
+
+impl<'a> StructSerializer<'a> {
+    pub(super) fn new(columns: &'a mut Columns) -> Self {
+        Self {
+            columns,
+            prefix: None,
+            key: None,
+        }
+    }
+
+    fn push<T>(&mut self, key: &str, value: &T) -> Result<(), super::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        if let (Some(prefix), Some(separator)) = (&self.prefix, &self.columns.separator) {
+            let name = format!("{prefix}{separator}{key}");
+            self.columns.push(&name, value)
+        } else {
+            self.columns.push(key, value)
+        }
+    }
+
+    fn end(self) {
+        // a flattened struct is a part of the enclosing row
+        if self.prefix.is_none() {
+            self.columns.end_row();
+        }
+    }
+}
+
+impl ser::SerializeStruct for StructSerializer<'_> {
+    type Ok = ();
+    type Error = super::Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        StructSerializer::end(self);
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for StructSerializer<'_> {
+    type Ok = ();
+    type Error = super::Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| <super::Error as ser::Error>::custom("value without a key"))?;
+        self.push(&key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        StructSerializer::end(self);
+        Ok(())
+    }
+}
+
+/// Appends a field of a row, flattening nested structs and maps into separate columns.
+struct FlattenSerializer<'a> {
+    columns: &'a mut Columns,
+    name: PlSmallStr,
+}
+
+impl<'a> FlattenSerializer<'a> {
+    fn value(self) -> ValueSerializer<'a> {
+        ValueSerializer(self.columns.builder(&self.name))
+    }
+}
+
+impl<'a> ser::Serializer for FlattenSerializer<'a> {
+    type Ok = ();
+    type Error = super::Error;
+    type SerializeSeq = <ValueSerializer<'a> as ser::Serializer>::SerializeSeq;
+    type SerializeTuple = <ValueSerializer<'a> as ser::Serializer>::SerializeTuple;
+    type SerializeTupleStruct = <ValueSerializer<'a> as ser::Serializer>::SerializeTupleStruct;
+    type SerializeTupleVariant = ser::Impossible<(), super::Error>;
+    type SerializeMap = StructSerializer<'a>;
+    type SerializeStruct = StructSerializer<'a>;
+    type SerializeStructVariant = ser::Impossible<(), super::Error>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_bool(self.value(), v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_i8(self.value(), v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_i16(self.value(), v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_i32(self.value(), v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_i64(self.value(), v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_i128(self.value(), v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_u8(self.value(), v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_u16(self.value(), v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_u32(self.value(), v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_u64(self.value(), v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_u128(self.value(), v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_f32(self.value(), v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_f64(self.value(), v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_char(self.value(), v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_str(self.value(), v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_bytes(self.value(), v)
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        self.value().serialize_none()
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        self.serialize_none()
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_unit_struct(self.value(), name)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::serialize_unit_variant(self.value(), name, variant_index, variant)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        // logical values of `Value` are single cells
+        if name.starts_with(crate::value::TOKEN_PREFIX) {
+            ser::Serializer::serialize_newtype_struct(self.value(), name, value)
+        } else {
+            value.serialize(self)
+        }
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        ser::Serializer::serialize_seq(self.value(), len)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        ser::Serializer::serialize_tuple(self.value(), len)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        ser::Serializer::serialize_tuple_struct(self.value(), name, len)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(StructSerializer {
+            columns: self.columns,
+            prefix: Some(self.name),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.serialize_map(None)
+    }
+
+    serialize_unsupported! {
+        serialize_newtype_variant
+        serialize_tuple_variant
+        serialize_struct_variant
+    }
+}
+
+/// Extracts a column name from a map key.
+struct KeySerializer;
+
+impl ser::Serializer for KeySerializer {
+    type Ok = PlSmallStr;
+    type Error = super::Error;
+    type SerializeSeq = ser::Impossible<PlSmallStr, super::Error>;
+    type SerializeTuple = ser::Impossible<PlSmallStr, super::Error>;
+    type SerializeTupleStruct = ser::Impossible<PlSmallStr, super::Error>;
+    type SerializeTupleVariant = ser::Impossible<PlSmallStr, super::Error>;
+    type SerializeMap = ser::Impossible<PlSmallStr, super::Error>;
+    type SerializeStruct = ser::Impossible<PlSmallStr, super::Error>;
+    type SerializeStructVariant = ser::Impossible<PlSmallStr, super::Error>;
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string().into())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(v.into())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(variant.into())
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    serialize_unsupported! {
+        serialize_bool
+        serialize_i8
+        serialize_i16
+        serialize_i32
+        serialize_i64
+        serialize_i128
+        serialize_u8
+        serialize_u16
+        serialize_u32
+        serialize_u64
+        serialize_u128
+        serialize_f32
+        serialize_f64
+        serialize_bytes
+        serialize_none
+        serialize_some
+        serialize_unit
+        serialize_unit_struct
+        serialize_newtype_variant
+        serialize_seq
+        serialize_tuple
+        serialize_tuple_struct
+        serialize_tuple_variant
+        serialize_map
+        serialize_struct
+        serialize_struct_variant
+    }
+}
@@ -0,0 +1,495 @@
+use serde::de;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::slice;
+
+/// What a type asks its deserializer for, seen through options and newtypes.
+#[derive(Debug)]
+pub(crate) enum Shape {
+    /// The fields passed to `deserialize_struct`.
+    Struct(&'static [&'static str]),
+    /// The variants passed to `deserialize_enum`.
+    #[cfg_attr(not(feature = "dtype-categorical"), allow(dead_code))]
+    Enum(&'static [&'static str]),
+}
+
+pub(crate) fn shape<'de, T>() -> Option<Shape>
+where
+    T: de::Deserialize<'de>,
+{
+    match T::deserialize(Tracer) {
+        Err(Trace(shape)) => shape,
+        Ok(_) => None,
+    }
+}
+
+struct Tracer;
+
+impl<'de> de::Deserializer<'de> for Tracer {
+    type Error = Trace;
+
+    fn deserialize_any<V>(self, _: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(Trace(None))
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(Trace(Some(Shape::Struct(fields))))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _: &'static str,
+        variants: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(Trace(Some(Shape::Enum(variants))))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map
+        identifier ignored_any
+    }
+}
+
+// the shape is returned as an error so that no value has to be made up
+#[derive(Debug)]
+struct Trace(Option<Shape>);
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("tracing a type")
+    }
+}
+
+impl std::error::Error for Trace {}
+
+impl de::Error for Trace {
+    fn custom<T>(_: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self(None)
+    }
+}
+
+/// The variants of every enum reachable from a type, by the enum's name.
+pub(crate) type Variants = HashMap<&'static str, &'static [&'static str]>;
+
+/// Walks `T` with made-up values and records the variants of each enum it asks for.
+///
+/// Only the first variant of an enum is walked into, and recursive types stop at a fixed depth.
+pub(crate) fn variants<'de, T>() -> Variants
+where
+    T: de::Deserialize<'de>,
+{
+    let variants = RefCell::default();
+    // a type that rejects the made-up values still keeps the enums seen before
+    let _ = T::deserialize(Walker {
+        variants: &variants,
+        depth: 0,
+    });
+    variants.into_inner()
+}
+
+macro_rules! walk_as {
+    ($($($method:ident)* => $target:ident,)*) => {
+        $($(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: de::Visitor<'de>,
+            {
+                self.$target(visitor)
+            }
+        )*)*
+    };
+}
+
+const MAX_DEPTH: usize = 32;
+
+#[derive(Clone, Copy)]
+struct Walker<'a> {
+    variants: &'a RefCell<Variants>,
+    depth: usize,
+}
+
+impl Walker<'_> {
+    fn nested(self) -> Result<Self, Trace> {
+        if self.depth < MAX_DEPTH {
+            Ok(Self {
+                depth: self.depth + 1,
+                ..self
+            })
+        } else {
+            Err(Trace(None))
+        }
+    }
+
+    // containers past the depth limit are empty so that recursive types end
+    fn len(self, len: usize) -> usize {
+        if self.depth < MAX_DEPTH { len } else { 0 }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Walker<'_> {
+    type Error = Trace;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_bool(false)
+    }
+
+    // one rather than zero, so that types such as `NonZeroU32` accept it
+    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_u8(1)
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_f64(1.0)
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_char(' ')
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_borrowed_str("")
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_borrowed_bytes(&[])
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        if self.depth < MAX_DEPTH {
+            visitor.visit_some(self.nested()?)
+        } else {
+            visitor.visit_none()
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self.nested()?)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_tuple(1, visitor)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(Elements {
+            len: self.len(len),
+            walker: self.nested()?,
+        })
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_map(Entries {
+            len: self.len(1),
+            walker: self.nested()?,
+        })
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_map(Fields {
+            fields: fields.iter(),
+            walker: self.nested()?,
+        })
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.variants.borrow_mut().insert(name, variants);
+        visitor.visit_enum(self.nested()?)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    walk_as! {
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i128 deserialize_u8
+        deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128 => deserialize_i64,
+        deserialize_f32 => deserialize_f64,
+        deserialize_string deserialize_identifier => deserialize_str,
+        deserialize_byte_buf => deserialize_bytes,
+    }
+}
+
+struct Elements<'a> {
+    len: usize,
+    walker: Walker<'a>,
+}
+
+impl<'de> de::SeqAccess<'de> for Elements<'_> {
+    type Error = Trace;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(self.walker).map(Some)
+    }
+}
+
+struct Entries<'a> {
+    len: usize,
+    walker: Walker<'a>,
+}
+
+impl<'de> de::MapAccess<'de> for Entries<'_> {
+    type Error = Trace;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(self.walker).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.walker)
+    }
+}
+
+struct Fields<'a> {
+    fields: slice::Iter<'static, &'static str>,
+    walker: Walker<'a>,
+}
+
+impl<'de> de::MapAccess<'de> for Fields<'_> {
+    type Error = Trace;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        self.fields
+            .next()
+            .map(|field| seed.deserialize(de::value::BorrowedStrDeserializer::new(field)))
+            .transpose()
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.walker)
+    }
+}
+
+// the first variant stands for the enum
+impl<'de> de::EnumAccess<'de> for Walker<'_> {
+    type Error = Trace;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        Ok((seed.deserialize(de::value::U32Deserializer::new(0))?, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Walker<'_> {
+    type Error = Trace;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        de::Deserializer::deserialize_struct(self, "", fields, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    #[test]
+    fn test_variants() {
+        #[allow(dead_code)]
+        #[derive(Deserialize)]
+        enum Shape {
+            Circle(f64),
+            Square { side: f64 },
+        }
+
+        #[allow(dead_code)]
+        #[derive(Deserialize)]
+        enum Color {
+            Red,
+            Green,
+        }
+
+        #[allow(dead_code)]
+        #[derive(Deserialize)]
+        struct Node {
+            shape: Shape,
+            colors: Vec<Option<Color>>,
+            children: Vec<Node>,
+            id: std::num::NonZeroU32,
+        }
+
+        let variants = super::variants::<Node>();
+        assert_eq!(variants["Shape"], ["Circle", "Square"]);
+        assert_eq!(variants["Color"], ["Red", "Green"]);
+    }
+}
@@ -0,0 +1,811 @@
+use polars_core::datatypes::AnyValue;
+#[cfg(any(feature = "dtype-datetime", feature = "dtype-duration"))]
+use polars_core::datatypes::TimeUnit;
+use serde::{de, ser};
+use std::fmt;
+
+// `Value` asks deserializers for a newtype struct of this name, and those that recognize it
+// present logical values as an enum of one of the tags below with their physical parts.
+// The same names mark logical values serialized into a frame.
+macro_rules! token_prefix {
+    () => {
+        "$polars_serde::"
+    };
+}
+pub(crate) const TOKEN_PREFIX: &str = token_prefix!();
+pub(crate) const TOKEN: &str = concat!(token_prefix!(), "Value");
+#[cfg(feature = "dtype-array")]
+pub(crate) const ARRAY: &str = concat!(token_prefix!(), "Array");
+#[cfg(feature = "dtype-categorical")]
+pub(crate) const CATEGORICAL: &str = concat!(token_prefix!(), "Categorical");
+#[cfg(feature = "dtype-categorical")]
+pub(crate) const ENUM: &str = concat!(token_prefix!(), "Enum");
+#[cfg(feature = "dtype-date")]
+pub(crate) const DATE: &str = concat!(token_prefix!(), "Date");
+#[cfg(feature = "dtype-datetime")]
+pub(crate) const DATETIME: &str = concat!(token_prefix!(), "Datetime");
+#[cfg(feature = "dtype-duration")]
+pub(crate) const DURATION: &str = concat!(token_prefix!(), "Duration");
+#[cfg(feature = "dtype-time")]
+pub(crate) const TIME: &str = concat!(token_prefix!(), "Time");
+#[cfg(feature = "dtype-decimal")]
+pub(crate) const DECIMAL: &str = concat!(token_prefix!(), "Decimal");
+
+/// An owned value of a cell, for frames whose schema is not known at compile time.
+///
+/// Logical values keep their data types when read from and written to a frame.
+/// Other deserializers and serializers see them as their labels, elements or physical values.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Value {
+    #[default]
+    Null,
+    Boolean(bool),
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    Int128(i128),
+    UInt8(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    UInt128(u128),
+    Float32(f32),
+    Float64(f64),
+    /// Days since the UNIX epoch.
+    #[cfg(feature = "dtype-date")]
+    Date(i32),
+    /// Time since the UNIX epoch, with its unit and time zone.
+    #[cfg(feature = "dtype-datetime")]
+    Datetime(i64, TimeUnit, Option<String>),
+    #[cfg(feature = "dtype-duration")]
+    Duration(i64, TimeUnit),
+    /// Nanoseconds since midnight.
+    #[cfg(feature = "dtype-time")]
+    Time(i64),
+    /// The unscaled value, with its precision and scale.
+    #[cfg(feature = "dtype-decimal")]
+    Decimal(i128, usize, usize),
+    String(String),
+    /// The label of a `Categorical` value.
+    #[cfg(feature = "dtype-categorical")]
+    Categorical(String),
+    /// The label of an `Enum` value.
+    #[cfg(feature = "dtype-categorical")]
+    Enum(String),
+    Binary(Vec<u8>),
+    List(Vec<Value>),
+    #[cfg(feature = "dtype-array")]
+    Array(Vec<Value>),
+    /// Fields in their original order.
+    Struct(Vec<(String, Value)>),
+}
+
+impl Value {
+    // the logical variants, for values that have one
+    pub(crate) fn logical(value: &AnyValue) -> Result<Option<Self>, crate::de::Error> {
+        #[cfg(feature = "dtype-categorical")]
+        fn label(
+            cat: polars_core::datatypes::CatSize,
+            mapping: &std::sync::Arc<polars_core::datatypes::CategoricalMapping>,
+        ) -> Result<String, crate::de::Error> {
+            match mapping.cat_to_str(cat) {
+                Some(v) => Ok(v.to_owned()),
+                None => Err(crate::de::Error::InvalidCategoricalId(cat, mapping.clone())),
+            }
+        }
+
+        Ok(match value {
+            #[cfg(feature = "dtype-date")]
+            AnyValue::Date(v) => Some(Self::Date(*v)),
+            #[cfg(feature = "dtype-datetime")]
+            AnyValue::Datetime(v, tu, tz) => {
+                Some(Self::Datetime(*v, *tu, tz.map(|tz| tz.as_str().to_owned())))
+            }
+            #[cfg(feature = "dtype-datetime")]
+            AnyValue::DatetimeOwned(v, tu, tz) => Some(Self::Datetime(
+                *v,
+                *tu,
+                tz.as_ref().map(|tz| tz.as_str().to_owned()),
+            )),
+            #[cfg(feature = "dtype-duration")]
+            AnyValue::Duration(v, tu) => Some(Self::Duration(*v, *tu)),
+            #[cfg(feature = "dtype-time")]
+            AnyValue::Time(v) => Some(Self::Time(*v)),
+            #[cfg(feature = "dtype-decimal")]
+            AnyValue::Decimal(v, precision, scale) => Some(Self::Decimal(*v, *precision, *scale)),
+            #[cfg(feature = "dtype-categorical")]
+            AnyValue::Categorical(cat, mapping) => Some(Self::Categorical(label(*cat, mapping)?)),
+            #[cfg(feature = "dtype-categorical")]
+            AnyValue::CategoricalOwned(cat, mapping) => {
+                Some(Self::Categorical(label(*cat, mapping)?))
+            }
+            #[cfg(feature = "dtype-categorical")]
+            AnyValue::Enum(cat, mapping) => Some(Self::Enum(label(*cat, mapping)?)),
+            #[cfg(feature = "dtype-categorical")]
+            AnyValue::EnumOwned(cat, mapping) => Some(Self::Enum(label(*cat, mapping)?)),
+            _ => None,
+        })
+    }
+
+    // the tag and physical parts of a logical variant
+    fn tagged(self) -> Result<(&'static str, Self), Self> {
+        match self {
+            #[cfg(feature = "dtype-date")]
+            Self::Date(v) => Ok((DATE, Self::Int32(v))),
+            #[cfg(feature = "dtype-datetime")]
+            Self::Datetime(v, tu, tz) => Ok((
+                DATETIME,
+                Self::List(vec![
+                    Self::Int64(v),
+                    Self::String(tu.to_ascii().to_owned()),
+                    tz.map_or(Self::Null, Self::String),
+                ]),
+            )),
+            #[cfg(feature = "dtype-duration")]
+            Self::Duration(v, tu) => Ok((
+                DURATION,
+                Self::List(vec![Self::Int64(v), Self::String(tu.to_ascii().to_owned())]),
+            )),
+            #[cfg(feature = "dtype-time")]
+            Self::Time(v) => Ok((TIME, Self::Int64(v))),
+            #[cfg(feature = "dtype-decimal")]
+            Self::Decimal(v, precision, scale) => Ok((
+                DECIMAL,
+                Self::List(vec![
+                    Self::Int128(v),
+                    Self::UInt64(precision as u64),
+                    Self::UInt64(scale as u64),
+                ]),
+            )),
+            #[cfg(feature = "dtype-categorical")]
+            Self::Categorical(v) => Ok((CATEGORICAL, Self::String(v))),
+            #[cfg(feature = "dtype-categorical")]
+            Self::Enum(v) => Ok((ENUM, Self::String(v))),
+            #[cfg(feature = "dtype-array")]
+            Self::Array(v) => Ok((ARRAY, Self::List(v))),
+            v => Err(v),
+        }
+    }
+}
+
+#[cfg(any(feature = "dtype-datetime", feature = "dtype-duration"))]
+pub(crate) fn time_unit(unit: &str) -> Option<TimeUnit> {
+    [
+        TimeUnit::Nanoseconds,
+        TimeUnit::Microseconds,
+        TimeUnit::Milliseconds,
+    ]
+    .into_iter()
+    .find(|tu| tu.to_ascii() == unit)
+}
+
+/// Formats the mantissa of a decimal with `scale` fractional digits, e.g. `-0.05` for `(-5, 2)`.
+#[cfg(feature = "dtype-decimal")]
+pub(crate) fn format_decimal(v: i128, scale: usize) -> String {
+    let digits = format!("{:0>width$}", v.unsigned_abs(), width = scale + 1);
+    let (int, frac) = digits.split_at(digits.len() - scale);
+    let sign = if v < 0 { "-" } else { "" };
+    if frac.is_empty() {
+        format!("{sign}{int}")
+    } else {
+        format!("{sign}{int}.{frac}")
+    }
+}
+
+/// Parses a decimal string into its mantissa at `scale`, failing rather than rounding extra digits.
+#[cfg(feature = "dtype-decimal")]
+pub(crate) fn parse_decimal(v: &str, scale: usize) -> Option<i128> {
+    let (negative, v) = match v.strip_prefix('-') {
+        Some(v) => (true, v),
+        None => (false, v.strip_prefix('+').unwrap_or(v)),
+    };
+    let (int, frac) = v.split_once('.').unwrap_or((v, ""));
+    if (int.is_empty() && frac.is_empty())
+        || frac.len() > scale
+        || !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let mut mantissa = 0_i128;
+    for b in int
+        .bytes()
+        .chain(frac.bytes())
+        .chain(std::iter::repeat_n(b'0', scale - frac.len()))
+    {
+        mantissa = mantissa
+            .checked_mul(10)?
+            .checked_add(i128::from(b - b'0'))?;
+    }
+    Some(if negative { -mantissa } else { mantissa })
+}
+
+pub(crate) fn visit_tagged<'de, V, D>(
+    visitor: V,
+    tag: &'static str,
+    payload: D,
+) -> Result<V::Value, crate::de::Error>
+where
+    V: de::Visitor<'de>,
+    D: de::IntoDeserializer<'de, crate::de::Error>,
+{
+    visitor.visit_enum(de::value::MapAccessDeserializer::new(
+        de::value::MapDeserializer::new(std::iter::once((tag, payload))),
+    ))
+}
+
+impl<'de> de::Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(TOKEN, Visitor)
+    }
+}
+
+struct Visitor;
+
+impl<'de> de::Visitor<'de> for Visitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Value::Boolean(v))
+    }
+
+    fn visit_i8<E>(self, v: i8) -> Result<Self::Value, E> {
+        Ok(Value::Int8(v))
+    }
+
+    fn visit_i16<E>(self, v: i16) -> Result<Self::Value, E> {
+        Ok(Value::Int16(v))
+    }
+
+    fn visit_i32<E>(self, v: i32) -> Result<Self::Value, E> {
+        Ok(Value::Int32(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Value::Int64(v))
+    }
+
+    fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E> {
+        Ok(Value::Int128(v))
+    }
+
+    fn visit_u8<E>(self, v: u8) -> Result<Self::Value, E> {
+        Ok(Value::UInt8(v))
+    }
+
+    fn visit_u16<E>(self, v: u16) -> Result<Self::Value, E> {
+        Ok(Value::UInt16(v))
+    }
+
+    fn visit_u32<E>(self, v: u32) -> Result<Self::Value, E> {
+        Ok(Value::UInt32(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Value::UInt64(v))
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E> {
+        Ok(Value::UInt128(v))
+    }
+
+    fn visit_f32<E>(self, v: f32) -> Result<Self::Value, E> {
+        Ok(Value::Float32(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Value::Float64(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Value::String(v.to_owned()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(Value::Binary(v.to_owned()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(Value::Binary(v))
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        de::Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(Value::List(values))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut fields = Vec::with_capacity(map.size_hint().unwrap_or_default());
+        while let Some(field) = map.next_entry()? {
+            fields.push(field);
+        }
+        Ok(Value::Struct(fields))
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::EnumAccess<'de>,
+    {
+        #[allow(unused_imports)]
+        use de::{Error, VariantAccess};
+
+        #[cfg(any(feature = "dtype-datetime", feature = "dtype-duration"))]
+        fn unit<E: de::Error>(unit: &str) -> Result<TimeUnit, E> {
+            time_unit(unit)
+                .ok_or_else(|| E::invalid_value(de::Unexpected::Str(unit), &"a time unit"))
+        }
+
+        #[allow(unused_variables)]
+        let (tag, variant) = data.variant::<String>()?;
+        match tag.as_str() {
+            #[cfg(feature = "dtype-date")]
+            DATE => variant.newtype_variant().map(Value::Date),
+            #[cfg(feature = "dtype-datetime")]
+            DATETIME => {
+                let (v, tu, tz): (_, String, _) = variant.newtype_variant()?;
+                Ok(Value::Datetime(v, unit(&tu)?, tz))
+            }
+            #[cfg(feature = "dtype-duration")]
+            DURATION => {
+                let (v, tu): (_, String) = variant.newtype_variant()?;
+                Ok(Value::Duration(v, unit(&tu)?))
+            }
+            #[cfg(feature = "dtype-time")]
+            TIME => variant.newtype_variant().map(Value::Time),
+            #[cfg(feature = "dtype-decimal")]
+            DECIMAL => {
+                let (v, precision, scale) = variant.newtype_variant()?;
+                Ok(Value::Decimal(v, precision, scale))
+            }
+            #[cfg(feature = "dtype-categorical")]
+            CATEGORICAL => variant.newtype_variant().map(Value::Categorical),
+            #[cfg(feature = "dtype-categorical")]
+            ENUM => variant.newtype_variant().map(Value::Enum),
+            #[cfg(feature = "dtype-array")]
+            ARRAY => variant.newtype_variant().map(Value::Array),
+            _ => Err(A::Error::unknown_variant(&tag, &[])),
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = crate::de::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            Self::Null => visitor.visit_none(),
+            Self::Boolean(v) => visitor.visit_bool(v),
+            Self::Int8(v) => visitor.visit_i8(v),
+            Self::Int16(v) => visitor.visit_i16(v),
+            Self::Int32(v) => visitor.visit_i32(v),
+            Self::Int64(v) => visitor.visit_i64(v),
+            Self::Int128(v) => visitor.visit_i128(v),
+            Self::UInt8(v) => visitor.visit_u8(v),
+            Self::UInt16(v) => visitor.visit_u16(v),
+            Self::UInt32(v) => visitor.visit_u32(v),
+            Self::UInt64(v) => visitor.visit_u64(v),
+            Self::UInt128(v) => visitor.visit_u128(v),
+            Self::Float32(v) => visitor.visit_f32(v),
+            Self::Float64(v) => visitor.visit_f64(v),
+            #[cfg(feature = "dtype-date")]
+            Self::Date(v) => visitor.visit_i32(v),
+            #[cfg(feature = "dtype-datetime")]
+            Self::Datetime(v, _, _) => visitor.visit_i64(v),
+            #[cfg(feature = "dtype-duration")]
+            Self::Duration(v, _) => visitor.visit_i64(v),
+            #[cfg(feature = "dtype-time")]
+            Self::Time(v) => visitor.visit_i64(v),
+            #[cfg(feature = "dtype-decimal")]
+            Self::Decimal(v, _, scale) => visitor.visit_string(format_decimal(v, scale)),
+            Self::String(v) => visitor.visit_string(v),
+            #[cfg(feature = "dtype-categorical")]
+            Self::Categorical(v) | Self::Enum(v) => visitor.visit_string(v),
+            Self::Binary(v) => visitor.visit_byte_buf(v),
+            Self::List(v) => visitor.visit_seq(de::value::SeqDeserializer::new(v.into_iter())),
+            #[cfg(feature = "dtype-array")]
+            Self::Array(v) => visitor.visit_seq(de::value::SeqDeserializer::new(v.into_iter())),
+            Self::Struct(v) => visitor.visit_map(de::value::MapDeserializer::new(v.into_iter())),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        if self == Self::Null {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        if name == TOKEN {
+            match self.tagged() {
+                Ok((tag, payload)) => visit_tagged(visitor, tag, payload),
+                Err(v) => visitor.visit_newtype_struct(v),
+            }
+        } else {
+            visitor.visit_newtype_struct(self)
+        }
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    // decimals are exact strings unless a float is asked for
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            #[cfg(feature = "dtype-decimal")]
+            Self::Decimal(v, _, scale) => {
+                visitor.visit_f64(format_decimal(v, scale).parse().unwrap_or(f64::NAN))
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> de::IntoDeserializer<'de, crate::de::Error> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl ser::Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match self {
+            Self::Null => serializer.serialize_none(),
+            Self::Boolean(v) => serializer.serialize_bool(*v),
+            Self::Int8(v) => serializer.serialize_i8(*v),
+            Self::Int16(v) => serializer.serialize_i16(*v),
+            Self::Int32(v) => serializer.serialize_i32(*v),
+            Self::Int64(v) => serializer.serialize_i64(*v),
+            Self::Int128(v) => serializer.serialize_i128(*v),
+            Self::UInt8(v) => serializer.serialize_u8(*v),
+            Self::UInt16(v) => serializer.serialize_u16(*v),
+            Self::UInt32(v) => serializer.serialize_u32(*v),
+            Self::UInt64(v) => serializer.serialize_u64(*v),
+            Self::UInt128(v) => serializer.serialize_u128(*v),
+            Self::Float32(v) => serializer.serialize_f32(*v),
+            Self::Float64(v) => serializer.serialize_f64(*v),
+            #[cfg(feature = "dtype-date")]
+            Self::Date(v) => serializer.serialize_newtype_struct(DATE, v),
+            #[cfg(feature = "dtype-datetime")]
+            Self::Datetime(v, tu, tz) => {
+                serializer.serialize_newtype_struct(DATETIME, &(v, tu.to_ascii(), tz))
+            }
+            #[cfg(feature = "dtype-duration")]
+            Self::Duration(v, tu) => {
+                serializer.serialize_newtype_struct(DURATION, &(v, tu.to_ascii()))
+            }
+            #[cfg(feature = "dtype-time")]
+            Self::Time(v) => serializer.serialize_newtype_struct(TIME, v),
+            #[cfg(feature = "dtype-decimal")]
+            Self::Decimal(v, precision, scale) => {
+                serializer.serialize_newtype_struct(DECIMAL, &(v, precision, scale))
+            }
+            Self::String(v) => serializer.serialize_str(v),
+            #[cfg(feature = "dtype-categorical")]
+            Self::Categorical(v) => serializer.serialize_newtype_struct(CATEGORICAL, v),
+            #[cfg(feature = "dtype-categorical")]
+            Self::Enum(v) => serializer.serialize_newtype_struct(ENUM, v),
+            Self::Binary(v) => serializer.serialize_bytes(v),
+            Self::List(v) => serializer.collect_seq(v),
+            // fixed-size, like `[T; N]`
+            #[cfg(feature = "dtype-array")]
+            Self::Array(v) => {
+                use ser::SerializeTuple;
+
+                let mut tuple = serializer.serialize_tuple(v.len())?;
+                for v in v {
+                    tuple.serialize_element(v)?;
+                }
+                tuple.end()
+            }
+            Self::Struct(v) => serializer.collect_map(v.iter().map(|(k, v)| (k, v))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Value;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_any_value() {
+        let value = Value::deserialize(crate::de::AnyValueDeserializer::new(
+            polars_core::datatypes::AnyValue::UInt64(42),
+        ))
+        .unwrap();
+        assert_eq!(value, Value::UInt64(42));
+    }
+
+    #[test]
+    fn test_deserializer() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Row {
+            name: String,
+            scores: Vec<Option<u8>>,
+        }
+
+        let value = Value::Struct(vec![
+            ("name".to_owned(), Value::String("Alice".to_owned())),
+            (
+                "scores".to_owned(),
+                Value::List(vec![Value::UInt8(90), Value::Null]),
+            ),
+        ]);
+        assert_eq!(
+            Row::deserialize(value).unwrap(),
+            Row {
+                name: "Alice".to_owned(),
+                scores: vec![Some(90), None],
+            },
+        );
+    }
+
+    #[cfg(feature = "rows")]
+    #[test]
+    fn test_round_trip() {
+        use polars_core::frame::DataFrame;
+        use polars_core::frame::column::Column;
+        use serde::Serialize;
+
+        let s1 = Column::new("Ocean".into(), ["Atlantic", "Indian"]);
+        let s2 = Column::new("Area (km²)".into(), [None, Some(70_560_000_u64)]);
+        let s3 = Column::new("Deep".into(), [Some(true), None]);
+        let df = DataFrame::new_infer_height(vec![s1, s2, s3]).unwrap();
+
+        let rows = Vec::<indexmap::IndexMap<String, Value>>::deserialize(
+            crate::de::DataFrameDeserializer::rows(&df),
+        )
+        .unwrap();
+        assert_eq!(rows[1]["Area (km²)"], Value::UInt64(70_560_000));
+        assert_eq!(rows[1]["Deep"], Value::Null);
+
+        let output = rows
+            .serialize(crate::ser::DataFrameSerializer::rows())
+            .unwrap();
+        assert_eq!(output, df);
+    }
+
+    #[cfg(all(
+        feature = "rows",
+        feature = "dtype-categorical",
+        feature = "dtype-array"
+    ))]
+    #[test]
+    fn test_round_trip_nested() {
+        use polars_core::datatypes::{Categories, DataType, FrozenCategories};
+        use polars_core::frame::DataFrame;
+        use polars_core::frame::column::Column;
+        use polars_core::prelude::NamedFrom;
+        use polars_core::series::Series;
+        use serde::Serialize;
+
+        let s1 = Column::new("Ocean".into(), ["Atlantic", "Indian", "Atlantic"])
+            .cast(&DataType::from_categories(Categories::global()))
+            .unwrap();
+        let s2 = Column::new("Size".into(), ["Large", "Small", "Small"])
+            .cast(&DataType::from_frozen_categories(
+                FrozenCategories::new(["Large", "Small"]).unwrap(),
+            ))
+            .unwrap();
+        let s3 = Column::new(
+            "Position".into(),
+            [
+                Series::new("".into(), [-30.0, 0.0]),
+                Series::new("".into(), [-20.0, 80.0]),
+                Series::new("".into(), [10.0, -40.0]),
+            ],
+        )
+        .cast(&DataType::Array(Box::new(DataType::Float64), 2))
+        .unwrap();
+        let df = DataFrame::new_infer_height(vec![s1, s2, s3]).unwrap();
+
+        let rows = Vec::<indexmap::IndexMap<String, Value>>::deserialize(
+            crate::de::DataFrameDeserializer::rows(&df),
+        )
+        .unwrap();
+        assert_eq!(rows[1]["Ocean"], Value::Categorical("Indian".to_owned()));
+        assert_eq!(rows[1]["Size"], Value::Enum("Small".to_owned()));
+        assert_eq!(
+            rows[1]["Position"],
+            Value::Array(vec![Value::Float64(-20.0), Value::Float64(80.0)]),
+        );
+        assert_eq!(
+            String::deserialize(rows[1]["Size"].clone()).unwrap(),
+            "Small",
+        );
+
+        let output = rows
+            .serialize(crate::ser::DataFrameSerializer::rows())
+            .unwrap();
+        assert_eq!(output, df);
+    }
+
+    #[cfg(all(
+        feature = "rows",
+        feature = "dtype-date",
+        feature = "dtype-datetime",
+        feature = "dtype-duration",
+        feature = "dtype-time",
+        feature = "dtype-decimal",
+    ))]
+    #[test]
+    fn test_round_trip_temporal() {
+        use polars_core::datatypes::{AnyValue, TimeUnit, TimeZone};
+        use polars_core::frame::DataFrame;
+        use polars_core::prelude::NamedFrom;
+        use polars_core::series::Series;
+        use serde::Serialize;
+
+        let s1 = Series::new("Date".into(), [Some(19_000), None]).into_date();
+        let s2 = Series::new("Datetime".into(), [1_700_000_000_000_i64, 0])
+            .into_datetime(TimeUnit::Milliseconds, Some(TimeZone::UTC));
+        let s3 = Series::new("Duration".into(), [90_000_000_i64, -1])
+            .into_duration(TimeUnit::Microseconds);
+        let s4 = Series::new("Time".into(), [3_600_000_000_000_i64, 0]).into_time();
+        let s5 = Series::new("Decimal".into(), [12_345_i128, -5])
+            .into_decimal(10, 2)
+            .unwrap();
+        let df =
+            DataFrame::new_infer_height([s1, s2, s3, s4, s5].into_iter().map(Into::into).collect())
+                .unwrap();
+
+        let rows = Vec::<indexmap::IndexMap<String, Value>>::deserialize(
+            crate::de::DataFrameDeserializer::rows(&df),
+        )
+        .unwrap();
+        assert_eq!(rows[0]["Date"], Value::Date(19_000));
+        assert_eq!(
+            rows[0]["Datetime"],
+            Value::Datetime(
+                1_700_000_000_000,
+                TimeUnit::Milliseconds,
+                Some("UTC".to_owned()),
+            ),
+        );
+        assert_eq!(rows[0]["Decimal"], Value::Decimal(12_345, 10, 2));
+        assert_eq!(
+            f64::deserialize(rows[0]["Decimal"].clone()).unwrap(),
+            123.45
+        );
+        assert_eq!(
+            i32::deserialize(crate::de::AnyValueDeserializer::new(AnyValue::Date(19_000))).unwrap(),
+            19_000,
+        );
+
+        let output = rows
+            .serialize(crate::ser::DataFrameSerializer::rows())
+            .unwrap();
+        assert_eq!(output, df);
+    }
+
+    // 38 significant digits, far beyond the 15 to 17 of an `f64`
+    #[cfg(all(feature = "rows", feature = "dtype-decimal"))]
+    #[test]
+    fn test_round_trip_decimal() {
+        use polars_core::datatypes::DataType;
+        use polars_core::frame::DataFrame;
+        use polars_core::prelude::NamedFrom;
+        use polars_core::schema::Schema;
+        use polars_core::series::Series;
+        use serde::Serialize;
+
+        let s1 = Series::new(
+            "Decimal".into(),
+            [12_345_678_901_234_567_890_123_456_789_012_345_678_i128, -5],
+        )
+        .into_decimal(38, 4)
+        .unwrap();
+        let df = DataFrame::new_infer_height(vec![s1.into()]).unwrap();
+
+        let rows = Vec::<indexmap::IndexMap<String, Value>>::deserialize(
+            crate::de::DataFrameDeserializer::rows(&df),
+        )
+        .unwrap();
+        assert_eq!(
+            rows[0]["Decimal"],
+            Value::Decimal(12_345_678_901_234_567_890_123_456_789_012_345_678, 38, 4),
+        );
+        assert_eq!(
+            rows.serialize(crate::ser::DataFrameSerializer::rows())
+                .unwrap(),
+            df,
+        );
+
+        // other formats see exact strings, which a `Decimal` schema parses back
+        let json = serde_json::to_value(crate::ser::SerializeDataFrame::rows(&df)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                { "Decimal": "1234567890123456789012345678901234.5678" },
+                { "Decimal": "-0.0005" },
+            ]),
+        );
+        let schema = Schema::from_iter([("Decimal".into(), DataType::Decimal(38, 4))]);
+        assert_eq!(
+            json.as_array()
+                .unwrap()
+                .serialize(crate::ser::DataFrameSerializer::rows().with_schema(schema))
+                .unwrap(),
+            df,
+        );
+        assert_eq!(
+            f64::deserialize(rows[1]["Decimal"].clone()).unwrap(),
+            -0.0005,
+        );
+    }
+}